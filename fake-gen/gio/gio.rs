@@ -27,6 +27,7 @@ extern crate grust;
 #[macro_use]
 extern crate bitflags;
 
+extern crate futures_core;
 extern crate gio_2_0_sys as ffi;
 extern crate glib_2_0_sys as glib_ffi;
 extern crate gobject_2_0_sys as gobject_ffi;
@@ -41,13 +42,20 @@ use grust::gtype::GType;
 use grust::object;
 use grust::quark;
 use grust::refcount;
-use grust::types::{gint, gpointer};
+use grust::types::{gboolean, gconstpointer, gint, gint64, gpointer, gsize};
+use grust::types::{gchar, gssize, gulong, FALSE};
 use grust::wrap;
 
+use std::ffi::{CStr, CString, OsStr, OsString};
 use std::fmt;
+use std::io;
 use std::mem;
+use std::path::{Path, PathBuf};
 use std::ptr;
 use std::result;
+use std::slice;
+use std::str;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[repr(C)]
 pub struct AsyncResult {
@@ -96,13 +104,256 @@ unsafe impl wrap::Wrapper for FileInputStream {
     type Raw = ffi::GFileInputStream;
 }
 
+#[repr(C)]
+pub struct OutputStream {
+    raw: ffi::GOutputStream
+}
+
+unsafe impl wrap::Wrapper for OutputStream {
+    type Raw = ffi::GOutputStream;
+}
+
+#[repr(C)]
+pub struct FileOutputStream {
+    raw: ffi::GFileOutputStream
+}
+
+unsafe impl wrap::Wrapper for FileOutputStream {
+    type Raw = ffi::GFileOutputStream;
+}
+
+#[repr(C)]
+pub struct FileInfo {
+    raw: ffi::GFileInfo
+}
+
+unsafe impl wrap::Wrapper for FileInfo {
+    type Raw = ffi::GFileInfo;
+}
+
+#[repr(C)]
+pub struct FileEnumerator {
+    raw: ffi::GFileEnumerator
+}
+
+unsafe impl wrap::Wrapper for FileEnumerator {
+    type Raw = ffi::GFileEnumerator;
+}
+
+#[repr(C)]
+pub struct FileMonitor {
+    raw: ffi::GFileMonitor
+}
+
+unsafe impl wrap::Wrapper for FileMonitor {
+    type Raw = ffi::GFileMonitor;
+}
+
+#[repr(C)]
+pub struct IOStream {
+    raw: ffi::GIOStream
+}
+
+unsafe impl wrap::Wrapper for IOStream {
+    type Raw = ffi::GIOStream;
+}
+
+#[repr(C)]
+pub struct FileIOStream {
+    raw: ffi::GFileIOStream
+}
+
+unsafe impl wrap::Wrapper for FileIOStream {
+    type Raw = ffi::GFileIOStream;
+}
+
+#[repr(C)]
+pub struct MemoryInputStream {
+    raw: ffi::GMemoryInputStream
+}
+
+unsafe impl wrap::Wrapper for MemoryInputStream {
+    type Raw = ffi::GMemoryInputStream;
+}
+
+#[repr(C)]
+pub struct MemoryOutputStream {
+    raw: ffi::GMemoryOutputStream
+}
+
+unsafe impl wrap::Wrapper for MemoryOutputStream {
+    type Raw = ffi::GMemoryOutputStream;
+}
+
+#[repr(C)]
+pub struct FilterInputStream {
+    raw: ffi::GFilterInputStream
+}
+
+unsafe impl wrap::Wrapper for FilterInputStream {
+    type Raw = ffi::GFilterInputStream;
+}
+
+#[repr(C)]
+pub struct BufferedInputStream {
+    raw: ffi::GBufferedInputStream
+}
+
+unsafe impl wrap::Wrapper for BufferedInputStream {
+    type Raw = ffi::GBufferedInputStream;
+}
+
+#[repr(C)]
+pub struct DataInputStream {
+    raw: ffi::GDataInputStream
+}
+
+unsafe impl wrap::Wrapper for DataInputStream {
+    type Raw = ffi::GDataInputStream;
+}
+
+#[repr(C)]
+pub struct FilterOutputStream {
+    raw: ffi::GFilterOutputStream
+}
+
+unsafe impl wrap::Wrapper for FilterOutputStream {
+    type Raw = ffi::GFilterOutputStream;
+}
+
+#[repr(C)]
+pub struct BufferedOutputStream {
+    raw: ffi::GBufferedOutputStream
+}
+
+unsafe impl wrap::Wrapper for BufferedOutputStream {
+    type Raw = ffi::GBufferedOutputStream;
+}
+
+#[repr(C)]
+pub struct DataOutputStream {
+    raw: ffi::GDataOutputStream
+}
+
+unsafe impl wrap::Wrapper for DataOutputStream {
+    type Raw = ffi::GDataOutputStream;
+}
+
+#[repr(C)]
+pub struct Converter {
+    raw: ffi::GConverter
+}
+
+unsafe impl wrap::Wrapper for Converter {
+    type Raw = ffi::GConverter;
+}
+
+#[repr(C)]
+pub struct ZlibCompressor {
+    raw: ffi::GZlibCompressor
+}
+
+unsafe impl wrap::Wrapper for ZlibCompressor {
+    type Raw = ffi::GZlibCompressor;
+}
+
+#[repr(C)]
+pub struct ZlibDecompressor {
+    raw: ffi::GZlibDecompressor
+}
+
+unsafe impl wrap::Wrapper for ZlibDecompressor {
+    type Raw = ffi::GZlibDecompressor;
+}
+
+#[repr(C)]
+pub struct CharsetConverter {
+    raw: ffi::GCharsetConverter
+}
+
+unsafe impl wrap::Wrapper for CharsetConverter {
+    type Raw = ffi::GCharsetConverter;
+}
+
+#[repr(C)]
+pub struct ConverterInputStream {
+    raw: ffi::GConverterInputStream
+}
+
+unsafe impl wrap::Wrapper for ConverterInputStream {
+    type Raw = ffi::GConverterInputStream;
+}
+
+#[repr(C)]
+pub struct ConverterOutputStream {
+    raw: ffi::GConverterOutputStream
+}
+
+unsafe impl wrap::Wrapper for ConverterOutputStream {
+    type Raw = ffi::GConverterOutputStream;
+}
+
+#[repr(C)]
+pub struct Seekable {
+    raw: ffi::GSeekable
+}
+
+unsafe impl wrap::Wrapper for Seekable {
+    type Raw = ffi::GSeekable;
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 #[repr(C)]
 pub enum IOErrorEnum {
     Failed = 0,
     NotFound = 1,
     Exists = 2,
-    // ...
+    IsDirectory = 3,
+    NotDirectory = 4,
+    NotEmpty = 5,
+    NotRegularFile = 6,
+    NotSymbolicLink = 7,
+    NotMountableFile = 8,
+    FilenameTooLong = 9,
+    InvalidFilename = 10,
+    TooManyLinks = 11,
+    NoSpace = 12,
+    InvalidArgument = 13,
+    PermissionDenied = 14,
+    NotSupported = 15,
+    NotMounted = 16,
+    AlreadyMounted = 17,
+    Closed = 18,
+    Cancelled = 19,
+    Pending = 20,
+    ReadOnly = 21,
+    CantCreateBackup = 22,
+    WrongEtag = 23,
+    TimedOut = 24,
+    WouldRecurse = 25,
+    Busy = 26,
+    WouldBlock = 27,
+    HostNotFound = 28,
+    WouldMerge = 29,
+    FailedHandled = 30,
+    TooManyOpenFiles = 31,
+    NotInitialized = 32,
+    AddressInUse = 33,
+    PartialInput = 34,
+    InvalidData = 35,
+    DbusError = 36,
+    HostUnreachable = 37,
+    NetworkUnreachable = 38,
+    ConnectionRefused = 39,
+    ProxyFailed = 40,
+    ProxyAuthFailed = 41,
+    ProxyNeedAuth = 42,
+    ProxyNotAllowed = 43,
+    BrokenPipe = 44,
+    NotConnected = 45,
+    MessageTooLarge = 46,
+    NoSuchDevice = 47,
+    DestinationUnset = 48,
 }
 
 impl enumeration::IntrospectedEnum for IOErrorEnum {
@@ -112,304 +363,7044 @@ impl enumeration::IntrospectedEnum for IOErrorEnum {
             0 => Ok(IOErrorEnum::Failed),
             1 => Ok(IOErrorEnum::NotFound),
             2 => Ok(IOErrorEnum::Exists),
+            3 => Ok(IOErrorEnum::IsDirectory),
+            4 => Ok(IOErrorEnum::NotDirectory),
+            5 => Ok(IOErrorEnum::NotEmpty),
+            6 => Ok(IOErrorEnum::NotRegularFile),
+            7 => Ok(IOErrorEnum::NotSymbolicLink),
+            8 => Ok(IOErrorEnum::NotMountableFile),
+            9 => Ok(IOErrorEnum::FilenameTooLong),
+            10 => Ok(IOErrorEnum::InvalidFilename),
+            11 => Ok(IOErrorEnum::TooManyLinks),
+            12 => Ok(IOErrorEnum::NoSpace),
+            13 => Ok(IOErrorEnum::InvalidArgument),
+            14 => Ok(IOErrorEnum::PermissionDenied),
+            15 => Ok(IOErrorEnum::NotSupported),
+            16 => Ok(IOErrorEnum::NotMounted),
+            17 => Ok(IOErrorEnum::AlreadyMounted),
+            18 => Ok(IOErrorEnum::Closed),
+            19 => Ok(IOErrorEnum::Cancelled),
+            20 => Ok(IOErrorEnum::Pending),
+            21 => Ok(IOErrorEnum::ReadOnly),
+            22 => Ok(IOErrorEnum::CantCreateBackup),
+            23 => Ok(IOErrorEnum::WrongEtag),
+            24 => Ok(IOErrorEnum::TimedOut),
+            25 => Ok(IOErrorEnum::WouldRecurse),
+            26 => Ok(IOErrorEnum::Busy),
+            27 => Ok(IOErrorEnum::WouldBlock),
+            28 => Ok(IOErrorEnum::HostNotFound),
+            29 => Ok(IOErrorEnum::WouldMerge),
+            30 => Ok(IOErrorEnum::FailedHandled),
+            31 => Ok(IOErrorEnum::TooManyOpenFiles),
+            32 => Ok(IOErrorEnum::NotInitialized),
+            33 => Ok(IOErrorEnum::AddressInUse),
+            34 => Ok(IOErrorEnum::PartialInput),
+            35 => Ok(IOErrorEnum::InvalidData),
+            36 => Ok(IOErrorEnum::DbusError),
+            37 => Ok(IOErrorEnum::HostUnreachable),
+            38 => Ok(IOErrorEnum::NetworkUnreachable),
+            39 => Ok(IOErrorEnum::ConnectionRefused),
+            40 => Ok(IOErrorEnum::ProxyFailed),
+            41 => Ok(IOErrorEnum::ProxyAuthFailed),
+            42 => Ok(IOErrorEnum::ProxyNeedAuth),
+            43 => Ok(IOErrorEnum::ProxyNotAllowed),
+            44 => Ok(IOErrorEnum::BrokenPipe),
+            45 => Ok(IOErrorEnum::NotConnected),
+            46 => Ok(IOErrorEnum::MessageTooLarge),
+            47 => Ok(IOErrorEnum::NoSuchDevice),
+            48 => Ok(IOErrorEnum::DestinationUnset),
+            _ => Err(enumeration::UnknownValue(v))
+        }
+    }
+
+    fn to_int(&self) -> gint {
+        *self as gint
+    }
+
+    fn name(&self) -> &'static str {
+        match *self {
+            IOErrorEnum::Failed => "failed",
+            IOErrorEnum::NotFound => "not-found",
+            IOErrorEnum::Exists => "exists",
+            IOErrorEnum::IsDirectory => "is-directory",
+            IOErrorEnum::NotDirectory => "not-directory",
+            IOErrorEnum::NotEmpty => "not-empty",
+            IOErrorEnum::NotRegularFile => "not-regular-file",
+            IOErrorEnum::NotSymbolicLink => "not-symbolic-link",
+            IOErrorEnum::NotMountableFile => "not-mountable-file",
+            IOErrorEnum::FilenameTooLong => "filename-too-long",
+            IOErrorEnum::InvalidFilename => "invalid-filename",
+            IOErrorEnum::TooManyLinks => "too-many-links",
+            IOErrorEnum::NoSpace => "no-space",
+            IOErrorEnum::InvalidArgument => "invalid-argument",
+            IOErrorEnum::PermissionDenied => "permission-denied",
+            IOErrorEnum::NotSupported => "not-supported",
+            IOErrorEnum::NotMounted => "not-mounted",
+            IOErrorEnum::AlreadyMounted => "already-mounted",
+            IOErrorEnum::Closed => "closed",
+            IOErrorEnum::Cancelled => "cancelled",
+            IOErrorEnum::Pending => "pending",
+            IOErrorEnum::ReadOnly => "read-only",
+            IOErrorEnum::CantCreateBackup => "cant-create-backup",
+            IOErrorEnum::WrongEtag => "wrong-etag",
+            IOErrorEnum::TimedOut => "timed-out",
+            IOErrorEnum::WouldRecurse => "would-recurse",
+            IOErrorEnum::Busy => "busy",
+            IOErrorEnum::WouldBlock => "would-block",
+            IOErrorEnum::HostNotFound => "host-not-found",
+            IOErrorEnum::WouldMerge => "would-merge",
+            IOErrorEnum::FailedHandled => "failed-handled",
+            IOErrorEnum::TooManyOpenFiles => "too-many-open-files",
+            IOErrorEnum::NotInitialized => "not-initialized",
+            IOErrorEnum::AddressInUse => "address-in-use",
+            IOErrorEnum::PartialInput => "partial-input",
+            IOErrorEnum::InvalidData => "invalid-data",
+            IOErrorEnum::DbusError => "dbus-error",
+            IOErrorEnum::HostUnreachable => "host-unreachable",
+            IOErrorEnum::NetworkUnreachable => "network-unreachable",
+            IOErrorEnum::ConnectionRefused => "connection-refused",
+            IOErrorEnum::ProxyFailed => "proxy-failed",
+            IOErrorEnum::ProxyAuthFailed => "proxy-auth-failed",
+            IOErrorEnum::ProxyNeedAuth => "proxy-need-auth",
+            IOErrorEnum::ProxyNotAllowed => "proxy-not-allowed",
+            IOErrorEnum::BrokenPipe => "broken-pipe",
+            IOErrorEnum::NotConnected => "not-connected",
+            IOErrorEnum::MessageTooLarge => "message-too-large",
+            IOErrorEnum::NoSuchDevice => "no-such-device",
+            IOErrorEnum::DestinationUnset => "destination-unset",
+        }
+    }
+}
+
+impl enumeration::EnumType for IOErrorEnum {
+    fn get_type() -> GType {
+        unsafe { GType::from_raw(ffi::g_io_error_enum_get_type()) }
+    }
+}
+
+impl error::Domain for IOErrorEnum {
+    fn domain() -> quark::Quark {
+        g_static_quark!(b"g-io-error-quark\0")
+    }
+}
+
+impl fmt::Display for IOErrorEnum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub enum FileType {
+    Unknown = 0,
+    Regular = 1,
+    Directory = 2,
+    SymbolicLink = 3,
+    Special = 4,
+    Shortcut = 5,
+    Mountable = 6,
+}
+
+impl enumeration::IntrospectedEnum for FileType {
+
+    fn from_int(v: gint) -> Result<Self, enumeration::UnknownValue> {
+        match v {
+            0 => Ok(FileType::Unknown),
+            1 => Ok(FileType::Regular),
+            2 => Ok(FileType::Directory),
+            3 => Ok(FileType::SymbolicLink),
+            4 => Ok(FileType::Special),
+            5 => Ok(FileType::Shortcut),
+            6 => Ok(FileType::Mountable),
+            _ => Err(enumeration::UnknownValue(v))
+        }
+    }
+
+    fn to_int(&self) -> gint {
+        *self as gint
+    }
+
+    fn name(&self) -> &'static str {
+        match *self {
+            FileType::Unknown => "unknown",
+            FileType::Regular => "regular",
+            FileType::Directory => "directory",
+            FileType::SymbolicLink => "symbolic-link",
+            FileType::Special => "special",
+            FileType::Shortcut => "shortcut",
+            FileType::Mountable => "mountable",
+        }
+    }
+}
+
+impl enumeration::EnumType for FileType {
+    fn get_type() -> GType {
+        unsafe { GType::from_raw(ffi::g_file_type_get_type()) }
+    }
+}
+
+impl fmt::Display for FileType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub enum FileAttributeType {
+    Invalid = 0,
+    String = 1,
+    ByteString = 2,
+    Boolean = 3,
+    Uint32 = 4,
+    Int32 = 5,
+    Uint64 = 6,
+    Int64 = 7,
+    Object = 8,
+    Stringv = 9,
+}
+
+impl enumeration::IntrospectedEnum for FileAttributeType {
+
+    fn from_int(v: gint) -> Result<Self, enumeration::UnknownValue> {
+        match v {
+            0 => Ok(FileAttributeType::Invalid),
+            1 => Ok(FileAttributeType::String),
+            2 => Ok(FileAttributeType::ByteString),
+            3 => Ok(FileAttributeType::Boolean),
+            4 => Ok(FileAttributeType::Uint32),
+            5 => Ok(FileAttributeType::Int32),
+            6 => Ok(FileAttributeType::Uint64),
+            7 => Ok(FileAttributeType::Int64),
+            8 => Ok(FileAttributeType::Object),
+            9 => Ok(FileAttributeType::Stringv),
+            _ => Err(enumeration::UnknownValue(v))
+        }
+    }
+
+    fn to_int(&self) -> gint {
+        *self as gint
+    }
+
+    fn name(&self) -> &'static str {
+        match *self {
+            FileAttributeType::Invalid => "invalid",
+            FileAttributeType::String => "string",
+            FileAttributeType::ByteString => "byte-string",
+            FileAttributeType::Boolean => "boolean",
+            FileAttributeType::Uint32 => "uint32",
+            FileAttributeType::Int32 => "int32",
+            FileAttributeType::Uint64 => "uint64",
+            FileAttributeType::Int64 => "int64",
+            FileAttributeType::Object => "object",
+            FileAttributeType::Stringv => "stringv",
+        }
+    }
+}
+
+impl enumeration::EnumType for FileAttributeType {
+    fn get_type() -> GType {
+        unsafe { GType::from_raw(ffi::g_file_attribute_type_get_type()) }
+    }
+}
+
+impl fmt::Display for FileAttributeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub enum FileMonitorEvent {
+    Changed = 0,
+    ChangesDoneHint = 1,
+    Deleted = 2,
+    Created = 3,
+    AttributeChanged = 4,
+    PreUnmount = 5,
+    Unmounted = 6,
+    Moved = 7,
+    Renamed = 8,
+    MovedIn = 9,
+    MovedOut = 10,
+}
+
+impl enumeration::IntrospectedEnum for FileMonitorEvent {
+
+    fn from_int(v: gint) -> Result<Self, enumeration::UnknownValue> {
+        match v {
+            0 => Ok(FileMonitorEvent::Changed),
+            1 => Ok(FileMonitorEvent::ChangesDoneHint),
+            2 => Ok(FileMonitorEvent::Deleted),
+            3 => Ok(FileMonitorEvent::Created),
+            4 => Ok(FileMonitorEvent::AttributeChanged),
+            5 => Ok(FileMonitorEvent::PreUnmount),
+            6 => Ok(FileMonitorEvent::Unmounted),
+            7 => Ok(FileMonitorEvent::Moved),
+            8 => Ok(FileMonitorEvent::Renamed),
+            9 => Ok(FileMonitorEvent::MovedIn),
+            10 => Ok(FileMonitorEvent::MovedOut),
+            _ => Err(enumeration::UnknownValue(v))
+        }
+    }
+
+    fn to_int(&self) -> gint {
+        *self as gint
+    }
+
+    fn name(&self) -> &'static str {
+        match *self {
+            FileMonitorEvent::Changed => "changed",
+            FileMonitorEvent::ChangesDoneHint => "changes-done-hint",
+            FileMonitorEvent::Deleted => "deleted",
+            FileMonitorEvent::Created => "created",
+            FileMonitorEvent::AttributeChanged => "attribute-changed",
+            FileMonitorEvent::PreUnmount => "pre-unmount",
+            FileMonitorEvent::Unmounted => "unmounted",
+            FileMonitorEvent::Moved => "moved",
+            FileMonitorEvent::Renamed => "renamed",
+            FileMonitorEvent::MovedIn => "moved-in",
+            FileMonitorEvent::MovedOut => "moved-out",
+        }
+    }
+}
+
+impl enumeration::EnumType for FileMonitorEvent {
+    fn get_type() -> GType {
+        unsafe { GType::from_raw(ffi::g_file_monitor_event_get_type()) }
+    }
+}
+
+impl fmt::Display for FileMonitorEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub enum DataStreamByteOrder {
+    BigEndian = 0,
+    LittleEndian = 1,
+    HostEndian = 2,
+}
+
+impl enumeration::IntrospectedEnum for DataStreamByteOrder {
+
+    fn from_int(v: gint) -> Result<Self, enumeration::UnknownValue> {
+        match v {
+            0 => Ok(DataStreamByteOrder::BigEndian),
+            1 => Ok(DataStreamByteOrder::LittleEndian),
+            2 => Ok(DataStreamByteOrder::HostEndian),
             _ => Err(enumeration::UnknownValue(v))
         }
     }
 
-    fn to_int(&self) -> gint {
-        *self as gint
+    fn to_int(&self) -> gint {
+        *self as gint
+    }
+
+    fn name(&self) -> &'static str {
+        match *self {
+            DataStreamByteOrder::BigEndian => "big-endian",
+            DataStreamByteOrder::LittleEndian => "little-endian",
+            DataStreamByteOrder::HostEndian => "host-endian",
+        }
+    }
+}
+
+impl enumeration::EnumType for DataStreamByteOrder {
+    fn get_type() -> GType {
+        unsafe { GType::from_raw(ffi::g_data_stream_byte_order_get_type()) }
+    }
+}
+
+impl fmt::Display for DataStreamByteOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub enum DataStreamNewlineType {
+    Lf = 0,
+    Cr = 1,
+    CrLf = 2,
+    Any = 3,
+}
+
+impl enumeration::IntrospectedEnum for DataStreamNewlineType {
+
+    fn from_int(v: gint) -> Result<Self, enumeration::UnknownValue> {
+        match v {
+            0 => Ok(DataStreamNewlineType::Lf),
+            1 => Ok(DataStreamNewlineType::Cr),
+            2 => Ok(DataStreamNewlineType::CrLf),
+            3 => Ok(DataStreamNewlineType::Any),
+            _ => Err(enumeration::UnknownValue(v))
+        }
+    }
+
+    fn to_int(&self) -> gint {
+        *self as gint
+    }
+
+    fn name(&self) -> &'static str {
+        match *self {
+            DataStreamNewlineType::Lf => "lf",
+            DataStreamNewlineType::Cr => "cr",
+            DataStreamNewlineType::CrLf => "cr-lf",
+            DataStreamNewlineType::Any => "any",
+        }
+    }
+}
+
+impl enumeration::EnumType for DataStreamNewlineType {
+    fn get_type() -> GType {
+        unsafe { GType::from_raw(ffi::g_data_stream_newline_type_get_type()) }
+    }
+}
+
+impl fmt::Display for DataStreamNewlineType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub enum ZlibCompressorFormat {
+    Zlib = 0,
+    Gzip = 1,
+    Raw = 2,
+}
+
+impl enumeration::IntrospectedEnum for ZlibCompressorFormat {
+
+    fn from_int(v: gint) -> Result<Self, enumeration::UnknownValue> {
+        match v {
+            0 => Ok(ZlibCompressorFormat::Zlib),
+            1 => Ok(ZlibCompressorFormat::Gzip),
+            2 => Ok(ZlibCompressorFormat::Raw),
+            _ => Err(enumeration::UnknownValue(v))
+        }
+    }
+
+    fn to_int(&self) -> gint {
+        *self as gint
+    }
+
+    fn name(&self) -> &'static str {
+        match *self {
+            ZlibCompressorFormat::Zlib => "zlib",
+            ZlibCompressorFormat::Gzip => "gzip",
+            ZlibCompressorFormat::Raw => "raw",
+        }
+    }
+}
+
+impl enumeration::EnumType for ZlibCompressorFormat {
+    fn get_type() -> GType {
+        unsafe { GType::from_raw(ffi::g_zlib_compressor_format_get_type()) }
+    }
+}
+
+impl fmt::Display for ZlibCompressorFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub enum ConverterResult {
+    Error = 0,
+    Converted = 1,
+    Finished = 2,
+    Flushed = 3,
+}
+
+impl enumeration::IntrospectedEnum for ConverterResult {
+
+    fn from_int(v: gint) -> Result<Self, enumeration::UnknownValue> {
+        match v {
+            0 => Ok(ConverterResult::Error),
+            1 => Ok(ConverterResult::Converted),
+            2 => Ok(ConverterResult::Finished),
+            3 => Ok(ConverterResult::Flushed),
+            _ => Err(enumeration::UnknownValue(v))
+        }
+    }
+
+    fn to_int(&self) -> gint {
+        *self as gint
+    }
+
+    fn name(&self) -> &'static str {
+        match *self {
+            ConverterResult::Error => "error",
+            ConverterResult::Converted => "converted",
+            ConverterResult::Finished => "finished",
+            ConverterResult::Flushed => "flushed",
+        }
+    }
+}
+
+impl enumeration::EnumType for ConverterResult {
+    fn get_type() -> GType {
+        unsafe { GType::from_raw(ffi::g_converter_result_get_type()) }
+    }
+}
+
+impl fmt::Display for ConverterResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+pub mod flags {
+
+    pub mod converter {
+        use grust::flags::prelude::*;
+        use ffi;
+
+        bitflags! {
+            pub flags Flags: ::grust::types::guint {
+                const NONE         = 0,
+                const INPUT_AT_END = 1,
+                const FLUSH        = 2,
+            }
+        }
+
+        impl IntrospectedFlags for Flags {
+
+            fn from_uint(v: guint) -> Result<Flags, UnknownFlags> {
+                Flags::from_bits(v)
+                    .ok_or_else(|| UnknownFlags::new(v, Flags::all().bits()))
+            }
+
+            #[inline]
+            fn to_uint(&self) -> guint {
+                self.bits()
+            }
+        }
+
+        impl FlagsType for Flags {
+            fn get_type() -> GType {
+                unsafe {
+                    let raw = ffi::g_converter_flags_get_type();
+                    GType::from_raw(raw)
+                }
+            }
+        }
+    }
+
+    pub mod file_attribute_info {
+        use grust::flags::prelude::*;
+        use ffi;
+
+        bitflags! {
+            pub flags Flags: ::grust::types::guint {
+                const NONE            = 0,
+                const COPY_WITH_FILE  = 1,
+                const COPY_WHEN_MOVED = 2,
+            }
+        }
+
+        impl IntrospectedFlags for Flags {
+
+            fn from_uint(v: guint) -> Result<Flags, UnknownFlags> {
+                Flags::from_bits(v)
+                    .ok_or_else(|| UnknownFlags::new(v, Flags::all().bits()))
+            }
+
+            #[inline]
+            fn to_uint(&self) -> guint {
+                self.bits()
+            }
+        }
+
+        impl FlagsType for Flags {
+            fn get_type() -> GType {
+                unsafe {
+                    let raw = ffi::g_file_attribute_info_flags_get_type();
+                    GType::from_raw(raw)
+                }
+            }
+        }
+    }
+
+    pub mod file_copy {
+        use grust::flags::prelude::*;
+        use ffi;
+
+        bitflags! {
+            pub flags Flags: ::grust::types::guint {
+                const NONE                 = 0,
+                const OVERWRITE            = 1,
+                const BACKUP               = 2,
+                const NOFOLLOW_SYMLINKS    = 4,
+                const ALL_METADATA         = 8,
+                const NO_FALLBACK_FOR_MOVE = 16,
+                const TARGET_DEFAULT_PERMS = 32,
+            }
+        }
+
+        impl IntrospectedFlags for Flags {
+
+            fn from_uint(v: guint) -> Result<Flags, UnknownFlags> {
+                Flags::from_bits(v)
+                    .ok_or_else(|| UnknownFlags::new(v, Flags::all().bits()))
+            }
+
+            #[inline]
+            fn to_uint(&self) -> guint {
+                self.bits()
+            }
+        }
+
+        impl FlagsType for Flags {
+            fn get_type() -> GType {
+                unsafe {
+                    let raw = ffi::g_file_copy_flags_get_type();
+                    GType::from_raw(raw)
+                }
+            }
+        }
+    }
+
+    pub mod file_create {
+        use grust::flags::prelude::*;
+        use ffi;
+
+        bitflags! {
+            pub flags Flags: ::grust::types::guint {
+                const NONE                = 0,
+                const PRIVATE             = 1,
+                const REPLACE_DESTINATION = 2,
+            }
+        }
+
+        impl IntrospectedFlags for Flags {
+
+            fn from_uint(v: guint) -> Result<Flags, UnknownFlags> {
+                Flags::from_bits(v)
+                    .ok_or_else(|| UnknownFlags::new(v, Flags::all().bits()))
+            }
+
+            #[inline]
+            fn to_uint(&self) -> guint {
+                self.bits()
+            }
+        }
+
+        impl FlagsType for Flags {
+            fn get_type() -> GType {
+                unsafe {
+                    let raw = ffi::g_file_create_flags_get_type();
+                    GType::from_raw(raw)
+                }
+            }
+        }
+    }
+
+    pub mod file_monitor {
+        use grust::flags::prelude::*;
+        use ffi;
+
+        bitflags! {
+            pub flags Flags: ::grust::types::guint {
+                const NONE             = 0,
+                const WATCH_MOUNTS     = 1,
+                const SEND_MOVED       = 2,
+                const WATCH_HARD_LINKS = 4,
+                const WATCH_MOVES      = 8,
+            }
+        }
+
+        impl IntrospectedFlags for Flags {
+
+            fn from_uint(v: guint) -> Result<Flags, UnknownFlags> {
+                Flags::from_bits(v)
+                    .ok_or_else(|| UnknownFlags::new(v, Flags::all().bits()))
+            }
+
+            #[inline]
+            fn to_uint(&self) -> guint {
+                self.bits()
+            }
+        }
+
+        impl FlagsType for Flags {
+            fn get_type() -> GType {
+                unsafe {
+                    let raw = ffi::g_file_monitor_flags_get_type();
+                    GType::from_raw(raw)
+                }
+            }
+        }
+    }
+
+    pub mod file_query_info {
+        use grust::flags::prelude::*;
+        use ffi;
+
+        bitflags! {
+            pub flags Flags: ::grust::types::guint {
+                const NONE              = 0,
+                const NOFOLLOW_SYMLINKS = 1,
+            }
+        }
+
+        impl IntrospectedFlags for Flags {
+
+            fn from_uint(v: guint) -> Result<Flags, UnknownFlags> {
+                Flags::from_bits(v)
+                    .ok_or_else(|| UnknownFlags::new(v, Flags::all().bits()))
+            }
+
+            #[inline]
+            fn to_uint(&self) -> guint {
+                self.bits()
+            }
+        }
+
+        impl FlagsType for Flags {
+            fn get_type() -> GType {
+                unsafe {
+                    let raw = ffi::g_file_query_info_flags_get_type();
+                    GType::from_raw(raw)
+                }
+            }
+        }
+    }
+
+    pub mod output_stream_splice {
+        use grust::flags::prelude::*;
+        use ffi;
+
+        bitflags! {
+            pub flags Flags: ::grust::types::guint {
+                const NONE         = 0,
+                const CLOSE_SOURCE = 1,
+                const CLOSE_TARGET = 2,
+            }
+        }
+
+        impl IntrospectedFlags for Flags {
+
+            fn from_uint(v: guint) -> Result<Flags, UnknownFlags> {
+                Flags::from_bits(v)
+                    .ok_or_else(|| UnknownFlags::new(v, Flags::all().bits()))
+            }
+
+            #[inline]
+            fn to_uint(&self) -> guint {
+                self.bits()
+            }
+        }
+
+        impl FlagsType for Flags {
+            fn get_type() -> GType {
+                unsafe {
+                    let raw = ffi::g_output_stream_splice_flags_get_type();
+                    GType::from_raw(raw)
+                }
+            }
+        }
+    }
+}
+
+pub use flags::converter::Flags as ConverterFlags;
+pub use flags::file_attribute_info::Flags as FileAttributeInfoFlags;
+pub use flags::file_copy::Flags as FileCopyFlags;
+pub use flags::file_create::Flags as FileCreateFlags;
+pub use flags::file_monitor::Flags as FileMonitorFlags;
+pub use flags::file_query_info::Flags as FileQueryInfoFlags;
+pub use flags::output_stream_splice::Flags as OutputStreamSpliceFlags;
+
+mod ready {
+    use ffi;
+    use glib;
+    use gobject_ffi;
+    use gobject;
+
+    use grust::types::gpointer;
+    use grust::wrap;
+    use std::mem;
+
+    pub extern "C" fn async_ready_callback<F>(source_object: *mut gobject_ffi::GObject,
+                                              res: *mut ffi::GAsyncResult,
+                                              user_data: gpointer)
+        where F: FnOnce(&gobject::Object, &super::AsyncResult)
+    {
+        let cb: Box<F> = unsafe { mem::transmute(user_data) };
+        let arg1 = unsafe { wrap::from_raw::<gobject::Object>(source_object) };
+        let arg2 = unsafe { wrap::from_raw::<super::AsyncResult>(res) };
+        glib::unwind::catch_panic_or_abort(move || cb(arg1, arg2));
+    }
+}
+
+fn cancellable_ptr(cancellable: Option<&Cancellable>)
+                   -> *mut ffi::GCancellable
+{
+    use grust::wrap::Wrapper;
+    match cancellable {
+        Some(c) => c.as_mut_ptr(),
+        None    => ptr::null_mut()
+    }
+}
+
+#[cfg(unix)]
+fn filename_to_cstring(name: &OsStr) -> CString {
+    use std::os::unix::ffi::OsStrExt;
+    CString::new(name.as_bytes())
+        .expect("file name contains a NUL byte")
+}
+
+// GLib uses UTF-8 for file names on Windows
+#[cfg(windows)]
+fn filename_to_cstring(name: &OsStr) -> CString {
+    CString::new(name.to_string_lossy().into_owned())
+        .expect("file name contains a NUL byte")
+}
+
+#[cfg(unix)]
+unsafe fn take_filename(ptr: *mut gchar) -> Option<OsString> {
+    use std::os::unix::ffi::OsStrExt;
+    if ptr.is_null() {
+        return None;
+    }
+    let name = OsStr::from_bytes(CStr::from_ptr(ptr).to_bytes()).to_owned();
+    glib_ffi::g_free(ptr as gpointer);
+    Some(name)
+}
+
+#[cfg(windows)]
+unsafe fn take_filename(ptr: *mut gchar) -> Option<OsString> {
+    if ptr.is_null() {
+        return None;
+    }
+    let name = String::from_utf8_lossy(CStr::from_ptr(ptr).to_bytes())
+               .into_owned();
+    glib_ffi::g_free(ptr as gpointer);
+    Some(OsString::from(name))
+}
+
+unsafe fn take_etag(etag: *mut gchar) -> Option<gstr::OwnedGStr> {
+    if etag.is_null() {
+        None
+    } else {
+        Some(gstr::OwnedGStr::from_ptr(etag))
+    }
+}
+
+unsafe fn take_contents(contents: *mut gchar,
+                        length: gsize,
+                        etag: *mut gchar)
+                        -> (glib::Bytes, Option<gstr::OwnedGStr>)
+{
+    let bytes = glib_ffi::g_bytes_new_take(contents as gpointer, length);
+    (glib::Bytes::from_raw(bytes), take_etag(etag))
+}
+
+unsafe fn take_data(data: *mut gchar, length: gsize) -> Option<Vec<u8>> {
+    if data.is_null() {
+        return None;
+    }
+    let vec = slice::from_raw_parts(data as *const u8, length as usize)
+              .to_vec();
+    glib_ffi::g_free(data as gpointer);
+    Some(vec)
+}
+
+fn fill_count(count: Option<usize>) -> gssize {
+    match count {
+        Some(n) => n as gssize,
+        None    => -1
+    }
+}
+
+fn sync_progress(progress: &mut Option<&mut dyn FnMut(i64, i64)>)
+                 -> (ffi::GFileProgressCallback, gpointer)
+{
+    match *progress {
+        Some(ref mut f) => {
+            let trampoline: extern "C" fn(gint64, gint64, gpointer)
+                = callback::file_progress_callback::<&mut dyn FnMut(i64, i64)>;
+            (Some(trampoline), f as *mut &mut dyn FnMut(i64, i64) as gpointer)
+        }
+        None => (None, ptr::null_mut())
+    }
+}
+
+fn ready_callback<F>(_: &F) -> ffi::GAsyncReadyCallback
+    where F: FnOnce(&gobject::Object, &AsyncResult)
+{
+    Some(ready::async_ready_callback::<F>)
+}
+
+mod callback {
+    use ffi;
+    use glib;
+    use gobject_ffi;
+
+    use grust::types::{gint, gint64, gpointer};
+    use grust::wrap;
+    use std::mem;
+
+    pub extern "C" fn cancelled_callback<F>(cancellable: *mut ffi::GCancellable,
+                                            user_data: gpointer)
+        where F: Fn(&super::Cancellable)
+    {
+        let cb: &F = unsafe { &*(user_data as *const F) };
+        let arg1 = unsafe { wrap::from_raw::<super::Cancellable>(cancellable) };
+        // The cancellable may be cancelled in any thread
+        glib::unwind::catch_panic_or_abort(|| cb(arg1));
+    }
+
+    pub extern "C" fn destroy_closure<F>(user_data: gpointer) {
+        let _: Box<F> = unsafe { mem::transmute(user_data) };
+    }
+
+    pub extern "C" fn closure_notify<F>(user_data: gpointer,
+                                        _closure: *mut gobject_ffi::GClosure)
+    {
+        let _: Box<F> = unsafe { mem::transmute(user_data) };
+    }
+
+    pub extern "C" fn file_monitor_changed_callback<F>(
+            monitor: *mut ffi::GFileMonitor,
+            file: *mut ffi::GFile,
+            other_file: *mut ffi::GFile,
+            event_type: ffi::GFileMonitorEvent,
+            user_data: gpointer)
+        where F: Fn(&super::FileMonitor,
+                    &super::File,
+                    Option<&super::File>,
+                    super::FileMonitorEvent)
+    {
+        use grust::enumeration::IntrospectedEnum;
+
+        let cb: &F = unsafe { &*(user_data as *const F) };
+        let arg1 = unsafe { wrap::from_raw::<super::FileMonitor>(monitor) };
+        let arg2 = unsafe { wrap::from_raw::<super::File>(file) };
+        let arg3 = if other_file.is_null() {
+            None
+        } else {
+            Some(unsafe { wrap::from_raw::<super::File>(other_file) })
+        };
+        let event = super::FileMonitorEvent::from_int(event_type as gint);
+        let event = match event {
+            Ok(event) => event,
+            Err(_) => return
+        };
+        glib::unwind::catch_panic_or_abort(|| cb(arg1, arg2, arg3, event));
+    }
+
+    pub extern "C" fn file_progress_callback<F>(current_num_bytes: gint64,
+                                                total_num_bytes: gint64,
+                                                user_data: gpointer)
+        where F: FnMut(i64, i64)
+    {
+        let cb: &mut F = unsafe { &mut *(user_data as *mut F) };
+        glib::unwind::catch_panic_or_abort(|| {
+            cb(current_num_bytes, total_num_bytes)
+        });
+    }
+
+    /// Owns a boxed progress closure passed to a C function.
+    ///
+    /// The closure must outlive all calls to the progress callback,
+    /// so this is moved into the completion callback and dropped there.
+    pub struct ProgressClosure<F> {
+        ptr: *mut F,
+    }
+
+    // The closure is only accessed from the thread running
+    // the operation's main context.
+    unsafe impl<F> Send for ProgressClosure<F> where F: Send { }
+
+    impl<F> ProgressClosure<F> {
+
+        pub fn new(f: F) -> ProgressClosure<F> {
+            ProgressClosure { ptr: Box::into_raw(Box::new(f)) }
+        }
+
+        pub fn as_gpointer(&self) -> gpointer {
+            self.ptr as gpointer
+        }
+    }
+
+    impl<F> Drop for ProgressClosure<F> {
+        fn drop(&mut self) {
+            let _ = unsafe { Box::from_raw(self.ptr) };
+        }
+    }
+}
+
+pub mod future {
+    use ffi;
+    use gobject;
+
+    use grust::error;
+    use grust::refcount::Ref;
+    use grust::types::{gint, gpointer};
+    use grust::wrap::Wrapper;
+
+    use futures_core::Stream;
+
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::future::Future;
+    use std::mem;
+    use std::pin::Pin;
+    use std::rc::Rc;
+    use std::task::{Context, Poll, Waker};
+
+    struct Shared<T> {
+        result: Option<Result<T, error::Error>>,
+        waker: Option<Waker>,
+        complete: bool,
+    }
+
+    /// A future resolving to the result of a GIO asynchronous operation.
+    ///
+    /// The operation is started when the future is created, and its
+    /// completion is dispatched by the thread-default main context
+    /// of the calling thread, so the future must be polled
+    /// by an executor that iterates that context.
+    /// Dropping the future before completion cancels the operation.
+    pub struct AsyncCall<T> {
+        shared: Rc<RefCell<Shared<T>>>,
+        cancellable: Ref<super::Cancellable>,
+    }
+
+    impl<T> AsyncCall<T> where T: 'static {
+
+        /// Starts an asynchronous operation.
+        ///
+        /// The closure `start` is called immediately with the
+        /// cancellable, the callback and the user data to pass to
+        /// the C function starting the operation. The closure `finish`
+        /// is called on completion to obtain the result.
+        pub unsafe fn new<S, F>(start: S, finish: F) -> AsyncCall<T>
+            where S: FnOnce(*mut ffi::GCancellable,
+                            ffi::GAsyncReadyCallback,
+                            gpointer),
+                  F: FnOnce(&gobject::Object, &super::AsyncResult)
+                            -> Result<T, error::Error>,
+                  F: 'static
+        {
+            let cancellable = super::Cancellable::new();
+            let shared = Rc::new(RefCell::new(Shared {
+                result: None,
+                waker: None,
+                complete: false,
+            }));
+            let cb_shared = shared.clone();
+            let callback = move |obj: &gobject::Object,
+                                 res: &super::AsyncResult| {
+                let result = finish(obj, res);
+                let waker = {
+                    let mut shared = cb_shared.borrow_mut();
+                    shared.result = Some(result);
+                    shared.complete = true;
+                    shared.waker.take()
+                };
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            };
+            let callback_fn = super::ready_callback(&callback);
+            let user_data: gpointer = mem::transmute(Box::new(callback));
+            start(cancellable.as_mut_ptr(), callback_fn, user_data);
+            AsyncCall { shared: shared, cancellable: cancellable }
+        }
+    }
+
+    impl<T> AsyncCall<T> {
+
+        /// The cancellable passed to the operation.
+        pub fn cancellable(&self) -> &super::Cancellable {
+            &*self.cancellable
+        }
+    }
+
+    impl<T> Future for AsyncCall<T> {
+        type Output = Result<T, error::Error>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+            let mut shared = self.shared.borrow_mut();
+            match shared.result.take() {
+                Some(result) => Poll::Ready(result),
+                None => {
+                    shared.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+
+    impl<T> Drop for AsyncCall<T> {
+        fn drop(&mut self) {
+            if !self.shared.borrow().complete {
+                unsafe {
+                    ffi::g_cancellable_cancel(self.cancellable.as_mut_ptr());
+                }
+            }
+        }
+    }
+
+    /// A stream of `FileInfo` batches read from a `FileEnumerator`.
+    ///
+    /// Each batch is requested with `g_file_enumerator_next_files_async`
+    /// once the previous one has been consumed. The stream ends when
+    /// the enumerator is exhausted or after the first error.
+    pub struct FileInfoStream {
+        enumerator: Ref<super::FileEnumerator>,
+        num_files: usize,
+        io_priority: gint,
+        pending: Option<AsyncCall<Vec<Ref<super::FileInfo>>>>,
+        done: bool,
+    }
+
+    impl FileInfoStream {
+
+        pub fn new(enumerator: &super::FileEnumerator,
+                   num_files: usize,
+                   io_priority: gint)
+                   -> FileInfoStream
+        {
+            FileInfoStream {
+                enumerator: Ref::new(enumerator),
+                num_files: num_files,
+                io_priority: io_priority,
+                pending: None,
+                done: false,
+            }
+        }
+    }
+
+    impl Stream for FileInfoStream {
+        type Item = Result<Vec<Ref<super::FileInfo>>, error::Error>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context)
+                     -> Poll<Option<Self::Item>>
+        {
+            if self.done {
+                return Poll::Ready(None);
+            }
+            if self.pending.is_none() {
+                let call = self.enumerator.next_files_future(self.num_files,
+                                                             self.io_priority);
+                self.pending = Some(call);
+            }
+            let res = match self.pending {
+                Some(ref mut call) => {
+                    match Pin::new(call).poll(cx) {
+                        Poll::Ready(res) => res,
+                        Poll::Pending => return Poll::Pending
+                    }
+                }
+                None => unreachable!()
+            };
+            self.pending = None;
+            match res {
+                Ok(ref batch) if batch.is_empty() => {
+                    self.done = true;
+                    Poll::Ready(None)
+                }
+                Ok(batch) => Poll::Ready(Some(Ok(batch))),
+                Err(err) => {
+                    self.done = true;
+                    Poll::Ready(Some(Err(err)))
+                }
+            }
+        }
+    }
+
+    /// A stream of lines read from a `DataInputStream`.
+    ///
+    /// Each line is requested with `g_data_input_stream_read_line_async`
+    /// once the previous one has been consumed. The stream ends at
+    /// the end of the input or after the first error.
+    pub struct LineStream {
+        stream: Ref<super::DataInputStream>,
+        io_priority: gint,
+        pending: Option<AsyncCall<Option<Vec<u8>>>>,
+        done: bool,
+    }
+
+    impl LineStream {
+
+        pub fn new(stream: &super::DataInputStream, io_priority: gint)
+                   -> LineStream
+        {
+            LineStream {
+                stream: Ref::new(stream),
+                io_priority: io_priority,
+                pending: None,
+                done: false,
+            }
+        }
+    }
+
+    impl Stream for LineStream {
+        type Item = Result<Vec<u8>, error::Error>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context)
+                     -> Poll<Option<Self::Item>>
+        {
+            if self.done {
+                return Poll::Ready(None);
+            }
+            if self.pending.is_none() {
+                let call = self.stream.read_line_future(self.io_priority);
+                self.pending = Some(call);
+            }
+            let res = match self.pending {
+                Some(ref mut call) => {
+                    match Pin::new(call).poll(cx) {
+                        Poll::Ready(res) => res,
+                        Poll::Pending => return Poll::Pending
+                    }
+                }
+                None => unreachable!()
+            };
+            self.pending = None;
+            match res {
+                Ok(Some(line)) => Poll::Ready(Some(Ok(line))),
+                Ok(None) => {
+                    self.done = true;
+                    Poll::Ready(None)
+                }
+                Err(err) => {
+                    self.done = true;
+                    Poll::Ready(Some(Err(err)))
+                }
+            }
+        }
+    }
+
+    struct MonitorQueue {
+        events: VecDeque<(Ref<super::File>,
+                          Option<Ref<super::File>>,
+                          super::FileMonitorEvent)>,
+        waker: Option<Waker>,
+    }
+
+    /// A stream of change events reported by a `FileMonitor`.
+    ///
+    /// Events are queued as the monitor emits them in its main
+    /// context, so the stream must be polled by an executor that
+    /// iterates that context.
+    pub struct FileMonitorStream {
+        queue: Rc<RefCell<MonitorQueue>>,
+        _handler: gobject::SignalHandler,
+    }
+
+    impl FileMonitorStream {
+
+        pub fn new(monitor: &super::FileMonitor) -> FileMonitorStream {
+            let queue = Rc::new(RefCell::new(MonitorQueue {
+                events: VecDeque::new(),
+                waker: None,
+            }));
+            let cb_queue = queue.clone();
+            let handler = monitor.connect_changed(
+                move |_, file, other_file, event| {
+                    let waker = {
+                        let mut queue = cb_queue.borrow_mut();
+                        queue.events.push_back((Ref::new(file),
+                                                other_file.map(Ref::new),
+                                                event));
+                        queue.waker.take()
+                    };
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                });
+            FileMonitorStream { queue: queue, _handler: handler }
+        }
+    }
+
+    impl Stream for FileMonitorStream {
+        type Item = (Ref<super::File>,
+                     Option<Ref<super::File>>,
+                     super::FileMonitorEvent);
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context)
+                     -> Poll<Option<Self::Item>>
+        {
+            let mut queue = self.queue.borrow_mut();
+            match queue.events.pop_front() {
+                Some(item) => Poll::Ready(Some(item)),
+                None => {
+                    queue.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+}
+
+pub mod cast {
+    use grust::object;
+
+    use gobject;
+
+    pub trait AsAsyncResult {
+        fn as_async_result(&self) -> &super::AsyncResult;
+    }
+
+    impl<T> AsAsyncResult for T where T: object::Upcast<super::AsyncResult> {
+
+        #[inline]
+        fn as_async_result(&self) -> &super::AsyncResult {
+            self.upcast()
+        }
+    }
+
+    pub trait AsFile {
+        fn as_file(&self) -> &super::File;
+    }
+
+    impl<T> AsFile for T where T: object::Upcast<super::File> {
+
+        #[inline]
+        fn as_file(&self) -> &super::File {
+            self.upcast()
+        }
+    }
+
+    pub trait AsCancellable : gobject::cast::AsObject {
+        fn as_cancellable(&self) -> &super::Cancellable;
+    }
+
+    impl<T> AsCancellable for T
+        where T: object::Upcast<super::Cancellable>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_cancellable(&self) -> &super::Cancellable {
+            self.upcast()
+        }
+    }
+
+    pub trait AsInputStream : gobject::cast::AsObject {
+        fn as_input_stream(&self) -> &super::InputStream;
+    }
+
+    impl<T> AsInputStream for T
+        where T: object::Upcast<super::InputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_input_stream(&self) -> &super::InputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsFileInputStream : AsInputStream {
+        fn as_file_input_stream(&self) -> &super::FileInputStream;
+    }
+
+    impl<T> AsFileInputStream for T
+        where T: object::Upcast<super::FileInputStream>,
+              T: object::Upcast<super::InputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_file_input_stream(&self) -> &super::FileInputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsOutputStream : gobject::cast::AsObject {
+        fn as_output_stream(&self) -> &super::OutputStream;
+    }
+
+    impl<T> AsOutputStream for T
+        where T: object::Upcast<super::OutputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_output_stream(&self) -> &super::OutputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsFileOutputStream : AsOutputStream {
+        fn as_file_output_stream(&self) -> &super::FileOutputStream;
+    }
+
+    impl<T> AsFileOutputStream for T
+        where T: object::Upcast<super::FileOutputStream>,
+              T: object::Upcast<super::OutputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_file_output_stream(&self) -> &super::FileOutputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsFileInfo : gobject::cast::AsObject {
+        fn as_file_info(&self) -> &super::FileInfo;
+    }
+
+    impl<T> AsFileInfo for T
+        where T: object::Upcast<super::FileInfo>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_file_info(&self) -> &super::FileInfo {
+            self.upcast()
+        }
+    }
+
+    pub trait AsFileEnumerator : gobject::cast::AsObject {
+        fn as_file_enumerator(&self) -> &super::FileEnumerator;
+    }
+
+    impl<T> AsFileEnumerator for T
+        where T: object::Upcast<super::FileEnumerator>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_file_enumerator(&self) -> &super::FileEnumerator {
+            self.upcast()
+        }
+    }
+
+    pub trait AsFileMonitor : gobject::cast::AsObject {
+        fn as_file_monitor(&self) -> &super::FileMonitor;
+    }
+
+    impl<T> AsFileMonitor for T
+        where T: object::Upcast<super::FileMonitor>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_file_monitor(&self) -> &super::FileMonitor {
+            self.upcast()
+        }
+    }
+
+    pub trait AsIOStream : gobject::cast::AsObject {
+        fn as_io_stream(&self) -> &super::IOStream;
+    }
+
+    impl<T> AsIOStream for T
+        where T: object::Upcast<super::IOStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_io_stream(&self) -> &super::IOStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsFileIOStream : AsIOStream {
+        fn as_file_io_stream(&self) -> &super::FileIOStream;
+    }
+
+    impl<T> AsFileIOStream for T
+        where T: object::Upcast<super::FileIOStream>,
+              T: object::Upcast<super::IOStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_file_io_stream(&self) -> &super::FileIOStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsMemoryInputStream : AsInputStream {
+        fn as_memory_input_stream(&self) -> &super::MemoryInputStream;
+    }
+
+    impl<T> AsMemoryInputStream for T
+        where T: object::Upcast<super::MemoryInputStream>,
+              T: object::Upcast<super::InputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_memory_input_stream(&self) -> &super::MemoryInputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsMemoryOutputStream : AsOutputStream {
+        fn as_memory_output_stream(&self) -> &super::MemoryOutputStream;
+    }
+
+    impl<T> AsMemoryOutputStream for T
+        where T: object::Upcast<super::MemoryOutputStream>,
+              T: object::Upcast<super::OutputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_memory_output_stream(&self) -> &super::MemoryOutputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsFilterInputStream : AsInputStream {
+        fn as_filter_input_stream(&self) -> &super::FilterInputStream;
+    }
+
+    impl<T> AsFilterInputStream for T
+        where T: object::Upcast<super::FilterInputStream>,
+              T: object::Upcast<super::InputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_filter_input_stream(&self) -> &super::FilterInputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsBufferedInputStream : AsFilterInputStream {
+        fn as_buffered_input_stream(&self) -> &super::BufferedInputStream;
+    }
+
+    impl<T> AsBufferedInputStream for T
+        where T: object::Upcast<super::BufferedInputStream>,
+              T: object::Upcast<super::FilterInputStream>,
+              T: object::Upcast<super::InputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_buffered_input_stream(&self) -> &super::BufferedInputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsDataInputStream : AsBufferedInputStream {
+        fn as_data_input_stream(&self) -> &super::DataInputStream;
+    }
+
+    impl<T> AsDataInputStream for T
+        where T: object::Upcast<super::DataInputStream>,
+              T: object::Upcast<super::BufferedInputStream>,
+              T: object::Upcast<super::FilterInputStream>,
+              T: object::Upcast<super::InputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_data_input_stream(&self) -> &super::DataInputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsFilterOutputStream : AsOutputStream {
+        fn as_filter_output_stream(&self) -> &super::FilterOutputStream;
+    }
+
+    impl<T> AsFilterOutputStream for T
+        where T: object::Upcast<super::FilterOutputStream>,
+              T: object::Upcast<super::OutputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_filter_output_stream(&self) -> &super::FilterOutputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsBufferedOutputStream : AsFilterOutputStream {
+        fn as_buffered_output_stream(&self) -> &super::BufferedOutputStream;
+    }
+
+    impl<T> AsBufferedOutputStream for T
+        where T: object::Upcast<super::BufferedOutputStream>,
+              T: object::Upcast<super::FilterOutputStream>,
+              T: object::Upcast<super::OutputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_buffered_output_stream(&self) -> &super::BufferedOutputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsDataOutputStream : AsFilterOutputStream {
+        fn as_data_output_stream(&self) -> &super::DataOutputStream;
+    }
+
+    impl<T> AsDataOutputStream for T
+        where T: object::Upcast<super::DataOutputStream>,
+              T: object::Upcast<super::FilterOutputStream>,
+              T: object::Upcast<super::OutputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_data_output_stream(&self) -> &super::DataOutputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsConverter {
+        fn as_converter(&self) -> &super::Converter;
+    }
+
+    impl<T> AsConverter for T where T: object::Upcast<super::Converter> {
+
+        #[inline]
+        fn as_converter(&self) -> &super::Converter {
+            self.upcast()
+        }
+    }
+
+    pub trait AsZlibCompressor : gobject::cast::AsObject {
+        fn as_zlib_compressor(&self) -> &super::ZlibCompressor;
+    }
+
+    impl<T> AsZlibCompressor for T
+        where T: object::Upcast<super::ZlibCompressor>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_zlib_compressor(&self) -> &super::ZlibCompressor {
+            self.upcast()
+        }
+    }
+
+    pub trait AsZlibDecompressor : gobject::cast::AsObject {
+        fn as_zlib_decompressor(&self) -> &super::ZlibDecompressor;
+    }
+
+    impl<T> AsZlibDecompressor for T
+        where T: object::Upcast<super::ZlibDecompressor>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_zlib_decompressor(&self) -> &super::ZlibDecompressor {
+            self.upcast()
+        }
+    }
+
+    pub trait AsCharsetConverter : gobject::cast::AsObject {
+        fn as_charset_converter(&self) -> &super::CharsetConverter;
+    }
+
+    impl<T> AsCharsetConverter for T
+        where T: object::Upcast<super::CharsetConverter>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_charset_converter(&self) -> &super::CharsetConverter {
+            self.upcast()
+        }
+    }
+
+    pub trait AsConverterInputStream : AsFilterInputStream {
+        fn as_converter_input_stream(&self) -> &super::ConverterInputStream;
+    }
+
+    impl<T> AsConverterInputStream for T
+        where T: object::Upcast<super::ConverterInputStream>,
+              T: object::Upcast<super::FilterInputStream>,
+              T: object::Upcast<super::InputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_converter_input_stream(&self) -> &super::ConverterInputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsConverterOutputStream : AsFilterOutputStream {
+        fn as_converter_output_stream(&self) -> &super::ConverterOutputStream;
+    }
+
+    impl<T> AsConverterOutputStream for T
+        where T: object::Upcast<super::ConverterOutputStream>,
+              T: object::Upcast<super::FilterOutputStream>,
+              T: object::Upcast<super::OutputStream>,
+              T: object::Upcast<gobject::Object>
+    {
+        #[inline]
+        fn as_converter_output_stream(&self) -> &super::ConverterOutputStream {
+            self.upcast()
+        }
+    }
+
+    pub trait AsSeekable {
+        fn as_seekable(&self) -> &super::Seekable;
+    }
+
+    impl<T> AsSeekable for T where T: object::Upcast<super::Seekable> {
+
+        #[inline]
+        fn as_seekable(&self) -> &super::Seekable {
+            self.upcast()
+        }
+    }
+}
+
+impl File {
+
+    pub fn new_for_path(path: &gstr::Utf8) -> refcount::Ref<File> {
+        unsafe {
+            let ret = ffi::g_file_new_for_path(path.as_ptr());
+            refcount::Ref::from_raw(ret)
+        }
+    }
+
+    /// Creates a `File` for a filesystem path, which need not be
+    /// valid UTF-8.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path contains a NUL byte.
+    pub fn from_path(path: &Path) -> refcount::Ref<File> {
+        let path = filename_to_cstring(path.as_os_str());
+        unsafe {
+            let ret = ffi::g_file_new_for_path(path.as_ptr());
+            refcount::Ref::from_raw(ret)
+        }
+    }
+
+    pub fn new_for_uri(uri: &gstr::Utf8) -> refcount::Ref<File> {
+        unsafe {
+            let ret = ffi::g_file_new_for_uri(uri.as_ptr());
+            refcount::Ref::from_raw(ret)
+        }
+    }
+
+    /// Creates a `File` from a command line argument, which may be
+    /// a path relative to the current directory, or a URI.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument contains a NUL byte.
+    pub fn new_for_commandline_arg(arg: &OsStr) -> refcount::Ref<File> {
+        let arg = filename_to_cstring(arg);
+        unsafe {
+            let ret = ffi::g_file_new_for_commandline_arg(arg.as_ptr());
+            refcount::Ref::from_raw(ret)
+        }
+    }
+
+    /// Creates a new file in the directory for temporary files
+    /// and opens it for reading and writing.
+    ///
+    /// The template `tmpl`, if given, must be a file name containing
+    /// a sequence of six `X` characters; otherwise a default template
+    /// is used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the template contains a NUL byte.
+    pub fn new_tmp(tmpl: Option<&OsStr>)
+                   -> result::Result<(refcount::Ref<File>,
+                                      refcount::Ref<FileIOStream>),
+                                     error::Error>
+    {
+        let tmpl = tmpl.map(filename_to_cstring);
+        let mut iostream: *mut ffi::GFileIOStream = ptr::null_mut();
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            ffi::g_file_new_tmp(tmpl.as_ref().map_or(ptr::null(),
+                                                     |s| s.as_ptr()),
+                                &mut iostream,
+                                &mut err)
+        };
+        if err.is_null() {
+            unsafe {
+                Ok((refcount::Ref::from_raw(ret),
+                    refcount::Ref::from_raw(iostream)))
+            }
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    /// Creates a `File` from a name as returned by
+    /// `get_parse_name()`: either a URI or a UTF-8 path.
+    pub fn parse_name(parse_name: &gstr::Utf8) -> refcount::Ref<File> {
+        unsafe {
+            let ret = ffi::g_file_parse_name(parse_name.as_ptr());
+            refcount::Ref::from_raw(ret)
+        }
+    }
+
+    /// Returns the local path of the file, or `None` if the file
+    /// is not backed by a native filesystem path.
+    pub fn get_path(&self) -> Option<gstr::OwnedGStr> {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_file_get_path(self.as_mut_ptr());
+            if ret.is_null() {
+                None
+            } else {
+                Some(gstr::OwnedGStr::from_ptr(ret))
+            }
+        }
+    }
+
+    /// Returns the local path of the file, with the filename bytes
+    /// preserved, or `None` if the file has no local path.
+    pub fn to_path_buf(&self) -> Option<PathBuf> {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_file_get_path(self.as_mut_ptr());
+            take_filename(ret).map(PathBuf::from)
+        }
+    }
+
+    pub fn get_uri(&self) -> gstr::OwnedGStr {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_file_get_uri(self.as_mut_ptr());
+            gstr::OwnedGStr::from_ptr(ret)
+        }
+    }
+
+    pub fn get_parse_name(&self) -> gstr::OwnedGStr {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_file_get_parse_name(self.as_mut_ptr());
+            gstr::OwnedGStr::from_ptr(ret)
+        }
+    }
+
+    /// Returns the base name of the file, with the filename bytes
+    /// preserved, or `None` if the file has no base name.
+    pub fn get_basename(&self) -> Option<OsString> {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_file_get_basename(self.as_mut_ptr());
+            take_filename(ret)
+        }
+    }
+
+    /// Returns the parent directory, or `None` if this file
+    /// represents the root.
+    pub fn get_parent(&self) -> Option<refcount::Ref<File>> {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_file_get_parent(self.as_mut_ptr());
+            if ret.is_null() {
+                None
+            } else {
+                Some(refcount::Ref::from_raw(ret))
+            }
+        }
+    }
+
+    /// Returns a child of this file with the given relative name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name contains a NUL byte.
+    pub fn get_child(&self, name: &Path) -> refcount::Ref<File> {
+        let name = filename_to_cstring(name.as_os_str());
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_file_get_child(self.as_mut_ptr(), name.as_ptr());
+            refcount::Ref::from_raw(ret)
+        }
+    }
+
+    /// Resolves a path relative to this file. If `relative_path`
+    /// is absolute, the result is a file for that path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path contains a NUL byte.
+    pub fn resolve_relative_path(&self, relative_path: &Path)
+                                 -> refcount::Ref<File>
+    {
+        let path = filename_to_cstring(relative_path.as_os_str());
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_file_resolve_relative_path(self.as_mut_ptr(),
+                                                        path.as_ptr());
+            refcount::Ref::from_raw(ret)
+        }
+    }
+
+    pub fn read_async<F>(&self,
+                         io_priority: gint,
+                         cancellable: Option<&Cancellable>,
+                         callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_file_read_async(self_raw,
+                                   io_priority,
+                                   cancellable_ptr(cancellable),
+                                   Some(ready::async_ready_callback::<F>),
+                                   callback);
+        }
+    }
+
+    pub fn read_finish(&self, res: &AsyncResult)
+                      -> result::Result<refcount::Ref<FileInputStream>,
+                                        error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_read_finish(self.as_mut_ptr(),
+                                    res.as_mut_ptr(),
+                                    &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn read_future(&self, io_priority: gint)
+                       -> future::AsyncCall<refcount::Ref<FileInputStream>>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_file_read_async(self_raw,
+                                           io_priority,
+                                           cancellable,
+                                           callback,
+                                           user_data)
+                },
+                |obj, res| {
+                    let f: &File = object::cast(obj);
+                    f.read_finish(res)
+                })
+        }
+    }
+
+    pub fn create(&self,
+                  flags: FileCreateFlags,
+                  cancellable: Option<&Cancellable>)
+                 -> result::Result<refcount::Ref<FileOutputStream>,
+                                   error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_create(self.as_mut_ptr(),
+                               flags.bits(),
+                               cancellable_ptr(cancellable),
+                               &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn create_async<F>(&self,
+                           flags: FileCreateFlags,
+                           io_priority: gint,
+                           cancellable: Option<&Cancellable>,
+                           callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_file_create_async(self_raw,
+                                     flags.bits(),
+                                     io_priority,
+                                     cancellable_ptr(cancellable),
+                                     Some(ready::async_ready_callback::<F>),
+                                     callback);
+        }
+    }
+
+    pub fn create_finish(&self, res: &AsyncResult)
+                       -> result::Result<refcount::Ref<FileOutputStream>,
+                                         error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_create_finish(self.as_mut_ptr(),
+                                      res.as_mut_ptr(),
+                                      &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn create_future(&self,
+                         flags: FileCreateFlags,
+                         io_priority: gint)
+                        -> future::AsyncCall<refcount::Ref<FileOutputStream>>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_file_create_async(self_raw,
+                                             flags.bits(),
+                                             io_priority,
+                                             cancellable,
+                                             callback,
+                                             user_data)
+                },
+                |obj, res| {
+                    let f: &File = object::cast(obj);
+                    f.create_finish(res)
+                })
+        }
+    }
+
+    pub fn replace(&self,
+                   etag: Option<&gstr::Utf8>,
+                   make_backup: bool,
+                   flags: FileCreateFlags,
+                   cancellable: Option<&Cancellable>)
+                  -> result::Result<refcount::Ref<FileOutputStream>,
+                                    error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_replace(self.as_mut_ptr(),
+                                etag.map_or(ptr::null(), |s| s.as_ptr()),
+                                make_backup as gboolean,
+                                flags.bits(),
+                                cancellable_ptr(cancellable),
+                                &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn replace_async<F>(&self,
+                            etag: Option<&gstr::Utf8>,
+                            make_backup: bool,
+                            flags: FileCreateFlags,
+                            io_priority: gint,
+                            cancellable: Option<&Cancellable>,
+                            callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_file_replace_async(self_raw,
+                                      etag.map_or(ptr::null(), |s| s.as_ptr()),
+                                      make_backup as gboolean,
+                                      flags.bits(),
+                                      io_priority,
+                                      cancellable_ptr(cancellable),
+                                      Some(ready::async_ready_callback::<F>),
+                                      callback);
+        }
+    }
+
+    pub fn replace_finish(&self, res: &AsyncResult)
+                        -> result::Result<refcount::Ref<FileOutputStream>,
+                                          error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_replace_finish(self.as_mut_ptr(),
+                                       res.as_mut_ptr(),
+                                       &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn replace_future(&self,
+                          etag: Option<&gstr::Utf8>,
+                          make_backup: bool,
+                          flags: FileCreateFlags,
+                          io_priority: gint)
+                         -> future::AsyncCall<refcount::Ref<FileOutputStream>>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let etag = etag.map_or(ptr::null(), |s| s.as_ptr());
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_file_replace_async(self_raw,
+                                              etag,
+                                              make_backup as gboolean,
+                                              flags.bits(),
+                                              io_priority,
+                                              cancellable,
+                                              callback,
+                                              user_data)
+                },
+                |obj, res| {
+                    let f: &File = object::cast(obj);
+                    f.replace_finish(res)
+                })
+        }
+    }
+
+    pub fn append_to(&self,
+                     flags: FileCreateFlags,
+                     cancellable: Option<&Cancellable>)
+                    -> result::Result<refcount::Ref<FileOutputStream>,
+                                      error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_append_to(self.as_mut_ptr(),
+                                  flags.bits(),
+                                  cancellable_ptr(cancellable),
+                                  &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn append_to_async<F>(&self,
+                              flags: FileCreateFlags,
+                              io_priority: gint,
+                              cancellable: Option<&Cancellable>,
+                              callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_file_append_to_async(self_raw,
+                                        flags.bits(),
+                                        io_priority,
+                                        cancellable_ptr(cancellable),
+                                        Some(ready::async_ready_callback::<F>),
+                                        callback);
+        }
+    }
+
+    pub fn append_to_finish(&self, res: &AsyncResult)
+                          -> result::Result<refcount::Ref<FileOutputStream>,
+                                            error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_append_to_finish(self.as_mut_ptr(),
+                                         res.as_mut_ptr(),
+                                         &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn append_to_future(&self,
+                            flags: FileCreateFlags,
+                            io_priority: gint)
+                           -> future::AsyncCall<refcount::Ref<FileOutputStream>>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_file_append_to_async(self_raw,
+                                                flags.bits(),
+                                                io_priority,
+                                                cancellable,
+                                                callback,
+                                                user_data)
+                },
+                |obj, res| {
+                    let f: &File = object::cast(obj);
+                    f.append_to_finish(res)
+                })
+        }
+    }
+
+    pub fn query_info(&self,
+                      attributes: &gstr::Utf8,
+                      flags: FileQueryInfoFlags,
+                      cancellable: Option<&Cancellable>)
+                     -> result::Result<refcount::Ref<FileInfo>, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_query_info(self.as_mut_ptr(),
+                                   attributes.as_ptr(),
+                                   flags.bits(),
+                                   cancellable_ptr(cancellable),
+                                   &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn query_info_async<F>(&self,
+                               attributes: &gstr::Utf8,
+                               flags: FileQueryInfoFlags,
+                               io_priority: gint,
+                               cancellable: Option<&Cancellable>,
+                               callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_file_query_info_async(self_raw,
+                                         attributes.as_ptr(),
+                                         flags.bits(),
+                                         io_priority,
+                                         cancellable_ptr(cancellable),
+                                         Some(ready::async_ready_callback::<F>),
+                                         callback);
+        }
+    }
+
+    pub fn query_info_finish(&self, res: &AsyncResult)
+                            -> result::Result<refcount::Ref<FileInfo>,
+                                              error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_query_info_finish(self.as_mut_ptr(),
+                                          res.as_mut_ptr(),
+                                          &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn query_info_future(&self,
+                             attributes: &gstr::Utf8,
+                             flags: FileQueryInfoFlags,
+                             io_priority: gint)
+                            -> future::AsyncCall<refcount::Ref<FileInfo>>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let attributes_raw = attributes.as_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_file_query_info_async(self_raw,
+                                                 attributes_raw,
+                                                 flags.bits(),
+                                                 io_priority,
+                                                 cancellable,
+                                                 callback,
+                                                 user_data)
+                },
+                |obj, res| {
+                    let f: &File = object::cast(obj);
+                    f.query_info_finish(res)
+                })
+        }
+    }
+
+    pub fn enumerate_children(&self,
+                              attributes: &gstr::Utf8,
+                              flags: FileQueryInfoFlags,
+                              cancellable: Option<&Cancellable>)
+                             -> result::Result<refcount::Ref<FileEnumerator>,
+                                               error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_enumerate_children(self.as_mut_ptr(),
+                                           attributes.as_ptr(),
+                                           flags.bits(),
+                                           cancellable_ptr(cancellable),
+                                           &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn enumerate_children_async<F>(&self,
+                                       attributes: &gstr::Utf8,
+                                       flags: FileQueryInfoFlags,
+                                       io_priority: gint,
+                                       cancellable: Option<&Cancellable>,
+                                       callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_file_enumerate_children_async(
+                    self_raw,
+                    attributes.as_ptr(),
+                    flags.bits(),
+                    io_priority,
+                    cancellable_ptr(cancellable),
+                    Some(ready::async_ready_callback::<F>),
+                    callback);
+        }
+    }
+
+    pub fn enumerate_children_finish(&self, res: &AsyncResult)
+                                    -> result::Result<
+                                            refcount::Ref<FileEnumerator>,
+                                            error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_enumerate_children_finish(self.as_mut_ptr(),
+                                                  res.as_mut_ptr(),
+                                                  &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn enumerate_children_future(&self,
+                                     attributes: &gstr::Utf8,
+                                     flags: FileQueryInfoFlags,
+                                     io_priority: gint)
+                                    -> future::AsyncCall<
+                                            refcount::Ref<FileEnumerator>>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let attributes_raw = attributes.as_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_file_enumerate_children_async(self_raw,
+                                                         attributes_raw,
+                                                         flags.bits(),
+                                                         io_priority,
+                                                         cancellable,
+                                                         callback,
+                                                         user_data)
+                },
+                |obj, res| {
+                    let f: &File = object::cast(obj);
+                    f.enumerate_children_finish(res)
+                })
+        }
+    }
+
+    /// Copies this file to `destination`.
+    ///
+    /// If `progress` is given, it is called synchronously with
+    /// the number of bytes copied so far and the total size.
+    pub fn copy(&self,
+                destination: &File,
+                flags: FileCopyFlags,
+                cancellable: Option<&Cancellable>,
+                progress: Option<&mut dyn FnMut(i64, i64)>)
+               -> result::Result<(), error::Error>
+    {
+        let mut progress = progress;
+        let (progress_fn, progress_data) = sync_progress(&mut progress);
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            // The progress callback is called synchronously in this thread
+            let _scope = glib::unwind::ResumeScope::enter();
+            ffi::g_file_copy(self.as_mut_ptr(),
+                             destination.as_mut_ptr(),
+                             flags.bits(),
+                             cancellable_ptr(cancellable),
+                             progress_fn,
+                             progress_data,
+                             &mut err)
+        };
+        glib::unwind::resume_if_panicked();
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    /// Starts copying this file to `destination` asynchronously.
+    ///
+    /// The `progress` closure is called in the thread-default main
+    /// context of the calling thread. It is dropped after `callback`
+    /// returns, whether the copy has succeeded, failed or
+    /// been cancelled.
+    pub fn copy_async<P, F>(&self,
+                            destination: &File,
+                            flags: FileCopyFlags,
+                            io_priority: gint,
+                            cancellable: Option<&Cancellable>,
+                            progress: P,
+                            callback: F)
+        where P: FnMut(i64, i64) + Send + 'static,
+              F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let progress = callback::ProgressClosure::new(progress);
+            let progress_data = progress.as_gpointer();
+            let callback = move |obj: &gobject::Object, res: &AsyncResult| {
+                let _progress = progress;
+                callback(obj, res)
+            };
+            let callback_fn = ready_callback(&callback);
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_file_copy_async(self_raw,
+                                   destination.as_mut_ptr(),
+                                   flags.bits(),
+                                   io_priority,
+                                   cancellable_ptr(cancellable),
+                                   Some(callback::file_progress_callback::<P>),
+                                   progress_data,
+                                   callback_fn,
+                                   callback);
+        }
+    }
+
+    pub fn copy_finish(&self, res: &AsyncResult)
+                      -> result::Result<(), error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_copy_finish(self.as_mut_ptr(),
+                                    res.as_mut_ptr(),
+                                    &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    /// Returns a future for `copy_async`.
+    ///
+    /// The `progress` closure is called in the thread polling
+    /// the future, as the copy proceeds.
+    pub fn copy_future<P>(&self,
+                          destination: &File,
+                          flags: FileCopyFlags,
+                          io_priority: gint,
+                          progress: P)
+                          -> future::AsyncCall<()>
+        where P: FnMut(i64, i64) + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let destination_raw = destination.as_mut_ptr();
+            let progress = callback::ProgressClosure::new(progress);
+            let progress_data = progress.as_gpointer();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_file_copy_async(
+                            self_raw,
+                            destination_raw,
+                            flags.bits(),
+                            io_priority,
+                            cancellable,
+                            Some(callback::file_progress_callback::<P>),
+                            progress_data,
+                            callback,
+                            user_data)
+                },
+                move |obj, res| {
+                    let _progress = progress;
+                    let f: &File = object::cast(obj);
+                    f.copy_finish(res)
+                })
+        }
+    }
+
+    /// Moves this file to `destination`.
+    ///
+    /// If `progress` is given, it is called synchronously when
+    /// the move has to fall back to copying.
+    pub fn move_(&self,
+                 destination: &File,
+                 flags: FileCopyFlags,
+                 cancellable: Option<&Cancellable>,
+                 progress: Option<&mut dyn FnMut(i64, i64)>)
+                -> result::Result<(), error::Error>
+    {
+        let mut progress = progress;
+        let (progress_fn, progress_data) = sync_progress(&mut progress);
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            // The progress callback is called synchronously in this thread
+            let _scope = glib::unwind::ResumeScope::enter();
+            ffi::g_file_move(self.as_mut_ptr(),
+                             destination.as_mut_ptr(),
+                             flags.bits(),
+                             cancellable_ptr(cancellable),
+                             progress_fn,
+                             progress_data,
+                             &mut err)
+        };
+        glib::unwind::resume_if_panicked();
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn delete(&self, cancellable: Option<&Cancellable>)
+                  -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_delete(self.as_mut_ptr(),
+                               cancellable_ptr(cancellable),
+                               &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn delete_async<F>(&self,
+                           io_priority: gint,
+                           cancellable: Option<&Cancellable>,
+                           callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_file_delete_async(self_raw,
+                                     io_priority,
+                                     cancellable_ptr(cancellable),
+                                     Some(ready::async_ready_callback::<F>),
+                                     callback);
+        }
+    }
+
+    pub fn delete_finish(&self, res: &AsyncResult)
+                        -> result::Result<(), error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_delete_finish(self.as_mut_ptr(),
+                                      res.as_mut_ptr(),
+                                      &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn delete_future(&self, io_priority: gint)
+                         -> future::AsyncCall<()>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_file_delete_async(self_raw,
+                                             io_priority,
+                                             cancellable,
+                                             callback,
+                                             user_data)
+                },
+                |obj, res| {
+                    let f: &File = object::cast(obj);
+                    f.delete_finish(res)
+                })
+        }
+    }
+
+    pub fn trash(&self, cancellable: Option<&Cancellable>)
+                 -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_trash(self.as_mut_ptr(),
+                              cancellable_ptr(cancellable),
+                              &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn trash_async<F>(&self,
+                          io_priority: gint,
+                          cancellable: Option<&Cancellable>,
+                          callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_file_trash_async(self_raw,
+                                    io_priority,
+                                    cancellable_ptr(cancellable),
+                                    Some(ready::async_ready_callback::<F>),
+                                    callback);
+        }
+    }
+
+    pub fn trash_finish(&self, res: &AsyncResult)
+                       -> result::Result<(), error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_trash_finish(self.as_mut_ptr(),
+                                     res.as_mut_ptr(),
+                                     &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn trash_future(&self, io_priority: gint)
+                        -> future::AsyncCall<()>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_file_trash_async(self_raw,
+                                            io_priority,
+                                            cancellable,
+                                            callback,
+                                            user_data)
+                },
+                |obj, res| {
+                    let f: &File = object::cast(obj);
+                    f.trash_finish(res)
+                })
+        }
+    }
+
+    pub fn make_directory(&self, cancellable: Option<&Cancellable>)
+                          -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_make_directory(self.as_mut_ptr(),
+                                       cancellable_ptr(cancellable),
+                                       &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn make_directory_async<F>(&self,
+                                   io_priority: gint,
+                                   cancellable: Option<&Cancellable>,
+                                   callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_file_make_directory_async(
+                    self_raw,
+                    io_priority,
+                    cancellable_ptr(cancellable),
+                    Some(ready::async_ready_callback::<F>),
+                    callback);
+        }
+    }
+
+    pub fn make_directory_finish(&self, res: &AsyncResult)
+                                -> result::Result<(), error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_make_directory_finish(self.as_mut_ptr(),
+                                              res.as_mut_ptr(),
+                                              &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn make_directory_future(&self, io_priority: gint)
+                                 -> future::AsyncCall<()>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_file_make_directory_async(self_raw,
+                                                     io_priority,
+                                                     cancellable,
+                                                     callback,
+                                                     user_data)
+                },
+                |obj, res| {
+                    let f: &File = object::cast(obj);
+                    f.make_directory_finish(res)
+                })
+        }
+    }
+
+    pub fn make_directory_with_parents(&self,
+                                       cancellable: Option<&Cancellable>)
+                                      -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_make_directory_with_parents(
+                    self.as_mut_ptr(),
+                    cancellable_ptr(cancellable),
+                    &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn make_symbolic_link(&self,
+                              symlink_value: &gstr::Utf8,
+                              cancellable: Option<&Cancellable>)
+                             -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_make_symbolic_link(self.as_mut_ptr(),
+                                           symlink_value.as_ptr(),
+                                           cancellable_ptr(cancellable),
+                                           &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn monitor_file(&self,
+                        flags: FileMonitorFlags,
+                        cancellable: Option<&Cancellable>)
+                       -> result::Result<refcount::Ref<FileMonitor>,
+                                         error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_monitor_file(self.as_mut_ptr(),
+                                     flags.bits(),
+                                     cancellable_ptr(cancellable),
+                                     &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn monitor_directory(&self,
+                             flags: FileMonitorFlags,
+                             cancellable: Option<&Cancellable>)
+                            -> result::Result<refcount::Ref<FileMonitor>,
+                                              error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_monitor_directory(self.as_mut_ptr(),
+                                          flags.bits(),
+                                          cancellable_ptr(cancellable),
+                                          &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    /// Loads the whole contents of the file.
+    ///
+    /// Returns the contents and the entity tag of the file,
+    /// if one is available.
+    pub fn load_contents(&self, cancellable: Option<&Cancellable>)
+                         -> result::Result<(glib::Bytes,
+                                            Option<gstr::OwnedGStr>),
+                                           error::Error>
+    {
+        let mut contents: *mut gchar = ptr::null_mut();
+        let mut length: gsize = 0;
+        let mut etag: *mut gchar = ptr::null_mut();
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_load_contents(self.as_mut_ptr(),
+                                      cancellable_ptr(cancellable),
+                                      &mut contents,
+                                      &mut length,
+                                      &mut etag,
+                                      &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { take_contents(contents, length, etag) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn load_contents_async<F>(&self,
+                                  cancellable: Option<&Cancellable>,
+                                  callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_file_load_contents_async(
+                    self_raw,
+                    cancellable_ptr(cancellable),
+                    Some(ready::async_ready_callback::<F>),
+                    callback);
+        }
+    }
+
+    pub fn load_contents_finish(&self, res: &AsyncResult)
+                               -> result::Result<(glib::Bytes,
+                                                  Option<gstr::OwnedGStr>),
+                                                 error::Error> {
+        let mut contents: *mut gchar = ptr::null_mut();
+        let mut length: gsize = 0;
+        let mut etag: *mut gchar = ptr::null_mut();
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_load_contents_finish(self.as_mut_ptr(),
+                                             res.as_mut_ptr(),
+                                             &mut contents,
+                                             &mut length,
+                                             &mut etag,
+                                             &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { take_contents(contents, length, etag) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn load_contents_future(&self)
+                                -> future::AsyncCall<(glib::Bytes,
+                                                      Option<gstr::OwnedGStr>)>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_file_load_contents_async(self_raw,
+                                                    cancellable,
+                                                    callback,
+                                                    user_data)
+                },
+                |obj, res| {
+                    let f: &File = object::cast(obj);
+                    f.load_contents_finish(res)
+                })
+        }
+    }
+
+    /// Replaces the contents of the file, returning the new
+    /// entity tag if one is available.
+    pub fn replace_contents(&self,
+                            contents: &[u8],
+                            etag: Option<&gstr::Utf8>,
+                            make_backup: bool,
+                            flags: FileCreateFlags,
+                            cancellable: Option<&Cancellable>)
+                           -> result::Result<Option<gstr::OwnedGStr>,
+                                             error::Error>
+    {
+        let mut new_etag: *mut gchar = ptr::null_mut();
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_replace_contents(self.as_mut_ptr(),
+                                         contents.as_ptr() as *const gchar,
+                                         contents.len() as gsize,
+                                         etag.map_or(ptr::null(),
+                                                     |s| s.as_ptr()),
+                                         make_backup as gboolean,
+                                         flags.bits(),
+                                         &mut new_etag,
+                                         cancellable_ptr(cancellable),
+                                         &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { take_etag(new_etag) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    /// Starts replacing the contents of the file asynchronously.
+    ///
+    /// The operation holds a reference to `contents` until it completes,
+    /// so the data are not copied.
+    pub fn replace_contents_bytes_async<F>(&self,
+                                           contents: &glib::Bytes,
+                                           etag: Option<&gstr::Utf8>,
+                                           make_backup: bool,
+                                           flags: FileCreateFlags,
+                                           cancellable: Option<&Cancellable>,
+                                           callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_file_replace_contents_bytes_async(
+                    self_raw,
+                    contents.as_mut_ptr(),
+                    etag.map_or(ptr::null(), |s| s.as_ptr()),
+                    make_backup as gboolean,
+                    flags.bits(),
+                    cancellable_ptr(cancellable),
+                    Some(ready::async_ready_callback::<F>),
+                    callback);
+        }
+    }
+
+    pub fn replace_contents_finish(&self, res: &AsyncResult)
+                                  -> result::Result<Option<gstr::OwnedGStr>,
+                                                    error::Error> {
+        let mut new_etag: *mut gchar = ptr::null_mut();
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_replace_contents_finish(self.as_mut_ptr(),
+                                                res.as_mut_ptr(),
+                                                &mut new_etag,
+                                                &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { take_etag(new_etag) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn replace_contents_bytes_future(&self,
+                                         contents: &glib::Bytes,
+                                         etag: Option<&gstr::Utf8>,
+                                         make_backup: bool,
+                                         flags: FileCreateFlags)
+                                        -> future::AsyncCall<
+                                                Option<gstr::OwnedGStr>>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let etag = etag.map_or(ptr::null(), |s| s.as_ptr());
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_file_replace_contents_bytes_async(
+                            self_raw,
+                            contents.as_mut_ptr(),
+                            etag,
+                            make_backup as gboolean,
+                            flags.bits(),
+                            cancellable,
+                            callback,
+                            user_data)
+                },
+                |obj, res| {
+                    let f: &File = object::cast(obj);
+                    f.replace_contents_finish(res)
+                })
+        }
+    }
+}
+
+impl FileEnumerator {
+
+    /// Returns information for the next file in the enumerated
+    /// directory, or `None` when there are no more files.
+    pub fn next_file(&self, cancellable: Option<&Cancellable>)
+                     -> result::Result<Option<refcount::Ref<FileInfo>>,
+                                       error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_enumerator_next_file(self.as_mut_ptr(),
+                                             cancellable_ptr(cancellable),
+                                             &mut err)
+        };
+        if !err.is_null() {
+            Err(unsafe { error::Error::from_raw(err) })
+        } else if ret.is_null() {
+            Ok(None)
+        } else {
+            Ok(Some(unsafe { refcount::Ref::from_raw(ret) }))
+        }
+    }
+
+    pub fn next_files_async<F>(&self,
+                               num_files: usize,
+                               io_priority: gint,
+                               cancellable: Option<&Cancellable>,
+                               callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_file_enumerator_next_files_async(
+                    self_raw,
+                    num_files as gint,
+                    io_priority,
+                    cancellable_ptr(cancellable),
+                    Some(ready::async_ready_callback::<F>),
+                    callback);
+        }
+    }
+
+    /// Finishes a batch started with `next_files_async()`.
+    ///
+    /// An empty vector is returned when the enumerator is exhausted.
+    pub fn next_files_finish(&self, res: &AsyncResult)
+                            -> result::Result<Vec<refcount::Ref<FileInfo>>,
+                                              error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let list = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_enumerator_next_files_finish(self.as_mut_ptr(),
+                                                     res.as_mut_ptr(),
+                                                     &mut err)
+        };
+        if !err.is_null() {
+            return Err(unsafe { error::Error::from_raw(err) });
+        }
+        let mut infos = Vec::new();
+        unsafe {
+            let mut node = list;
+            while !node.is_null() {
+                let info = (*node).data as *mut ffi::GFileInfo;
+                infos.push(refcount::Ref::from_raw(info));
+                node = (*node).next;
+            }
+            glib_ffi::g_list_free(list);
+        }
+        Ok(infos)
+    }
+
+    pub fn next_files_future(&self, num_files: usize, io_priority: gint)
+                             -> future::AsyncCall<Vec<refcount::Ref<FileInfo>>>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_file_enumerator_next_files_async(self_raw,
+                                                            num_files as gint,
+                                                            io_priority,
+                                                            cancellable,
+                                                            callback,
+                                                            user_data)
+                },
+                |obj, res| {
+                    let e: &FileEnumerator = object::cast(obj);
+                    e.next_files_finish(res)
+                })
+        }
+    }
+
+    /// Returns a stream yielding the remaining files in batches
+    /// of up to `num_files`.
+    pub fn stream(&self, num_files: usize, io_priority: gint)
+                  -> future::FileInfoStream
+    {
+        future::FileInfoStream::new(self, num_files, io_priority)
+    }
+
+    pub fn close(&self, cancellable: Option<&Cancellable>)
+                 -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_enumerator_close(self.as_mut_ptr(),
+                                         cancellable_ptr(cancellable),
+                                         &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_enumerator_is_closed(self.as_mut_ptr()) != FALSE
+        }
+    }
+
+    /// Returns the directory being enumerated.
+    pub fn get_container(&self) -> &File {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_file_enumerator_get_container(self.as_mut_ptr());
+            wrap::from_raw(ret)
+        }
+    }
+
+    /// Returns the `File` for an entry returned by this enumerator.
+    pub fn get_child(&self, info: &FileInfo) -> refcount::Ref<File> {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_file_enumerator_get_child(self.as_mut_ptr(),
+                                                       info.as_mut_ptr());
+            refcount::Ref::from_raw(ret)
+        }
+    }
+}
+
+/// Iterates over the remaining entries synchronously, calling
+/// `next_file()` without a cancellable.
+impl<'a> IntoIterator for &'a FileEnumerator {
+    type Item = result::Result<refcount::Ref<FileInfo>, error::Error>;
+    type IntoIter = FileInfos<'a>;
+
+    fn into_iter(self) -> FileInfos<'a> {
+        FileInfos { enumerator: self, done: false }
+    }
+}
+
+/// An iterator over the entries of a `FileEnumerator`.
+///
+/// The iteration stops after the first error.
+pub struct FileInfos<'a> {
+    enumerator: &'a FileEnumerator,
+    done: bool,
+}
+
+impl<'a> Iterator for FileInfos<'a> {
+    type Item = result::Result<refcount::Ref<FileInfo>, error::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.enumerator.next_file(None) {
+            Ok(Some(info)) => Some(Ok(info)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl FileMonitor {
+
+    /// Cancels the monitor. Returns `true` if it has been
+    /// cancelled by this call.
+    pub fn cancel(&self) -> bool {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_monitor_cancel(self.as_mut_ptr()) != FALSE
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_monitor_is_cancelled(self.as_mut_ptr()) != FALSE
+        }
+    }
+
+    /// Sets the minimal interval between reports of changes
+    /// to the same file, in milliseconds.
+    pub fn set_rate_limit(&self, limit_msecs: gint) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_monitor_set_rate_limit(self.as_mut_ptr(), limit_msecs);
+        }
+    }
+
+    /// Connects a closure to the `changed` signal.
+    ///
+    /// The closure receives the monitor, the changed file, the other
+    /// file involved in a move or rename event, and the event type.
+    /// Signals are emitted in the thread-default main context
+    /// in which the monitor was created. Events with types
+    /// unknown to these bindings are not passed to the closure.
+    /// The closure is disconnected when the returned guard is dropped.
+    pub fn connect_changed<F>(&self, callback: F) -> gobject::SignalHandler
+        where F: Fn(&FileMonitor, &File, Option<&File>, FileMonitorEvent),
+              F: 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            use gobject::cast::AsObject;
+            let callback: gpointer = mem::transmute(Box::new(callback));
+            let handler_fn: extern "C" fn(*mut ffi::GFileMonitor,
+                                          *mut ffi::GFile,
+                                          *mut ffi::GFile,
+                                          ffi::GFileMonitorEvent,
+                                          gpointer)
+                = callback::file_monitor_changed_callback::<F>;
+            let id = gobject_ffi::g_signal_connect_data(
+                            self.as_mut_ptr() as gpointer,
+                            g_utf8!("changed").as_ptr(),
+                            Some(mem::transmute(handler_fn)),
+                            callback,
+                            Some(callback::closure_notify::<F>),
+                            0);
+            gobject::SignalHandler::new(self.as_object(),
+                                        gobject::SignalHandlerId::from_raw(id))
+        }
+    }
+
+    /// Returns a stream of change events reported by this monitor.
+    ///
+    /// Each item holds the changed file, the other file involved
+    /// in a move or rename event, and the event type.
+    /// The stream never ends; drop it to stop receiving events.
+    pub fn changes(&self) -> future::FileMonitorStream {
+        future::FileMonitorStream::new(self)
+    }
+}
+
+impl Cancellable {
+
+    pub fn new() -> refcount::Ref<Cancellable> {
+        unsafe {
+            let ret = ffi::g_cancellable_new();
+            refcount::Ref::from_raw(ret)
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_cancellable_is_cancelled(self.as_mut_ptr()) != FALSE
+        }
+    }
+
+    pub fn set_error_if_cancelled(&self) -> result::Result<(), error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_cancellable_set_error_if_cancelled(self.as_mut_ptr(),
+                                                      &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn cancel(&self) {
+        {
+            // Handlers are called synchronously in this thread
+            let _scope = glib::unwind::ResumeScope::enter();
+            unsafe {
+                use grust::wrap::Wrapper;
+                ffi::g_cancellable_cancel(self.as_mut_ptr());
+            }
+        }
+        glib::unwind::resume_if_panicked();
+    }
+
+    pub fn reset(&self) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_cancellable_reset(self.as_mut_ptr());
+        }
+    }
+
+    /// Connects a closure to be called when the cancellable is cancelled.
+    ///
+    /// If the cancellable is already cancelled, the closure is called
+    /// immediately, unlike with `connect_cancelled`. The closure is
+    /// disconnected when the returned guard is dropped.
+    /// A panic in the closure aborts the process if the cancellable
+    /// is cancelled in a thread where the panic cannot be resumed;
+    /// see `glib::unwind::catch_panic_or_abort`.
+    pub fn connect<F>(&self, callback: F) -> CancelledHandler
+        where F: Fn(&Cancellable) + Send + Sync + 'static
+    {
+        let _scope = glib::unwind::ResumeScope::enter();
+        let handler = unsafe {
+            use grust::wrap::Wrapper;
+            let callback: gpointer = mem::transmute(Box::new(callback));
+            let handler_fn: extern "C" fn(*mut ffi::GCancellable, gpointer)
+                = callback::cancelled_callback::<F>;
+            let id = ffi::g_cancellable_connect(
+                            self.as_mut_ptr(),
+                            Some(mem::transmute(handler_fn)),
+                            callback,
+                            Some(callback::destroy_closure::<F>));
+            CancelledHandler {
+                cancellable: refcount::Ref::new(self),
+                id: id
+            }
+        };
+        // The closure is called here if already cancelled
+        glib::unwind::resume_if_panicked();
+        handler
+    }
+}
+
+/// Keeps a closure connected to a `Cancellable`.
+///
+/// The closure is disconnected when this guard is dropped.
+pub struct CancelledHandler {
+    cancellable: refcount::Ref<Cancellable>,
+    id: gulong,
+}
+
+impl CancelledHandler {
+
+    pub fn disconnect(self) {
+        // Disconnects in drop()
+    }
+}
+
+impl Drop for CancelledHandler {
+    fn drop(&mut self) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_cancellable_disconnect(self.cancellable.as_mut_ptr(),
+                                          self.id);
+        }
+    }
+}
+
+impl InputStream {
+
+    pub fn read(&self, buffer: &mut [u8], cancellable: Option<&Cancellable>)
+                -> result::Result<usize, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_input_stream_read(self.as_mut_ptr(),
+                                     buffer.as_mut_ptr() as gpointer,
+                                     buffer.len() as gsize,
+                                     cancellable_ptr(cancellable),
+                                     &mut err)
+        };
+        if err.is_null() {
+            Ok(ret as usize)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn skip(&self, count: usize, cancellable: Option<&Cancellable>)
+                -> result::Result<usize, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_input_stream_skip(self.as_mut_ptr(),
+                                     count as gsize,
+                                     cancellable_ptr(cancellable),
+                                     &mut err)
+        };
+        if err.is_null() {
+            Ok(ret as usize)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn close(&self, cancellable: Option<&Cancellable>)
+                 -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_input_stream_close(self.as_mut_ptr(),
+                                      cancellable_ptr(cancellable),
+                                      &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    /// Reads until `buffer` is full or the end of the stream is reached.
+    ///
+    /// Returns the number of bytes read, which is less than the
+    /// length of `buffer` only if the end of the stream was reached.
+    pub fn read_all(&self,
+                    buffer: &mut [u8],
+                    cancellable: Option<&Cancellable>)
+                    -> result::Result<usize, error::Error>
+    {
+        let mut bytes_read: gsize = 0;
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_input_stream_read_all(self.as_mut_ptr(),
+                                         buffer.as_mut_ptr() as gpointer,
+                                         buffer.len() as gsize,
+                                         &mut bytes_read,
+                                         cancellable_ptr(cancellable),
+                                         &mut err)
+        };
+        if err.is_null() {
+            Ok(bytes_read as usize)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    /// Asynchronously reads until `buffer` is full or the end
+    /// of the stream is reached.
+    ///
+    /// The buffer is owned by the operation while it is in progress
+    /// and is passed back to `callback`, which should call
+    /// `read_all_finish` to learn how many bytes were read into it.
+    pub fn read_all_async<F>(&self,
+                             buffer: Vec<u8>,
+                             io_priority: gint,
+                             cancellable: Option<&Cancellable>,
+                             callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult, Vec<u8>),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let mut buffer = buffer;
+            let data = buffer.as_mut_ptr() as gpointer;
+            let count = buffer.len() as gsize;
+            let callback = move |obj: &gobject::Object, res: &AsyncResult| {
+                callback(obj, res, buffer)
+            };
+            let callback_fn = ready_callback(&callback);
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_input_stream_read_all_async(self_raw,
+                                               data,
+                                               count,
+                                               io_priority,
+                                               cancellable_ptr(cancellable),
+                                               callback_fn,
+                                               callback);
+        }
+    }
+
+    pub fn read_all_finish(&self, res: &AsyncResult)
+                           -> result::Result<usize, error::Error> {
+        let mut bytes_read: gsize = 0;
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_input_stream_read_all_finish(self.as_mut_ptr(),
+                                                res.as_mut_ptr(),
+                                                &mut bytes_read,
+                                                &mut err)
+        };
+        if err.is_null() {
+            Ok(bytes_read as usize)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    /// Reads up to `count` bytes, stopping short only at the end
+    /// of the stream. The future resolves to the data read.
+    pub fn read_all_future(&self, count: usize, io_priority: gint)
+                           -> future::AsyncCall<Vec<u8>>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let mut buffer = vec![0u8; count];
+            let data = buffer.as_mut_ptr() as gpointer;
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_input_stream_read_all_async(self_raw,
+                                                       data,
+                                                       count as gsize,
+                                                       io_priority,
+                                                       cancellable,
+                                                       callback,
+                                                       user_data)
+                },
+                move |obj, res| {
+                    let s: &InputStream = object::cast(obj);
+                    s.read_all_finish(res).map(|n| {
+                        buffer.truncate(n);
+                        buffer
+                    })
+                })
+        }
+    }
+
+    /// Reads up to `count` bytes into a newly allocated `Bytes` buffer.
+    ///
+    /// An empty buffer is returned at the end of the stream.
+    pub fn read_bytes(&self, count: usize, cancellable: Option<&Cancellable>)
+                      -> result::Result<glib::Bytes, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_input_stream_read_bytes(self.as_mut_ptr(),
+                                           count as gsize,
+                                           cancellable_ptr(cancellable),
+                                           &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { glib::Bytes::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn read_bytes_async<F>(&self,
+                               count: usize,
+                               io_priority: gint,
+                               cancellable: Option<&Cancellable>,
+                               callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_input_stream_read_bytes_async(
+                    self_raw,
+                    count as gsize,
+                    io_priority,
+                    cancellable_ptr(cancellable),
+                    Some(ready::async_ready_callback::<F>),
+                    callback);
+        }
+    }
+
+    pub fn read_bytes_finish(&self, res: &AsyncResult)
+                             -> result::Result<glib::Bytes, error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_input_stream_read_bytes_finish(self.as_mut_ptr(),
+                                                  res.as_mut_ptr(),
+                                                  &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { glib::Bytes::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn read_bytes_future(&self, count: usize, io_priority: gint)
+                             -> future::AsyncCall<glib::Bytes>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_input_stream_read_bytes_async(self_raw,
+                                                         count as gsize,
+                                                         io_priority,
+                                                         cancellable,
+                                                         callback,
+                                                         user_data)
+                },
+                |obj, res| {
+                    let s: &InputStream = object::cast(obj);
+                    s.read_bytes_finish(res)
+                })
+        }
+    }
+}
+
+impl OutputStream {
+
+    pub fn write(&self, buffer: &[u8], cancellable: Option<&Cancellable>)
+                 -> result::Result<usize, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_output_stream_write(self.as_mut_ptr(),
+                                       buffer.as_ptr() as gconstpointer,
+                                       buffer.len() as gsize,
+                                       cancellable_ptr(cancellable),
+                                       &mut err)
+        };
+        if err.is_null() {
+            Ok(ret as usize)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn flush(&self, cancellable: Option<&Cancellable>)
+                 -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_output_stream_flush(self.as_mut_ptr(),
+                                       cancellable_ptr(cancellable),
+                                       &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn close(&self, cancellable: Option<&Cancellable>)
+                 -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_output_stream_close(self.as_mut_ptr(),
+                                       cancellable_ptr(cancellable),
+                                       &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_output_stream_is_closed(self.as_mut_ptr()) != FALSE
+        }
+    }
+
+    /// Writes all of `buffer`, retrying short writes until done.
+    ///
+    /// Returns the number of bytes written, which on success
+    /// is the length of `buffer`.
+    pub fn write_all(&self, buffer: &[u8], cancellable: Option<&Cancellable>)
+                     -> result::Result<usize, error::Error>
+    {
+        let mut bytes_written: gsize = 0;
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_output_stream_write_all(self.as_mut_ptr(),
+                                           buffer.as_ptr() as gconstpointer,
+                                           buffer.len() as gsize,
+                                           &mut bytes_written,
+                                           cancellable_ptr(cancellable),
+                                           &mut err)
+        };
+        if err.is_null() {
+            Ok(bytes_written as usize)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    /// Asynchronously writes all of `data`.
+    ///
+    /// The data is kept alive by the operation until `callback`
+    /// has been called.
+    pub fn write_all_async<F>(&self,
+                              data: glib::Bytes,
+                              io_priority: gint,
+                              cancellable: Option<&Cancellable>,
+                              callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let buffer = data.as_ptr() as gconstpointer;
+            let count = data.len() as gsize;
+            let callback = move |obj: &gobject::Object, res: &AsyncResult| {
+                let _data = data;
+                callback(obj, res)
+            };
+            let callback_fn = ready_callback(&callback);
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_output_stream_write_all_async(self_raw,
+                                                 buffer,
+                                                 count,
+                                                 io_priority,
+                                                 cancellable_ptr(cancellable),
+                                                 callback_fn,
+                                                 callback);
+        }
+    }
+
+    pub fn write_all_finish(&self, res: &AsyncResult)
+                            -> result::Result<usize, error::Error> {
+        let mut bytes_written: gsize = 0;
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_output_stream_write_all_finish(self.as_mut_ptr(),
+                                                  res.as_mut_ptr(),
+                                                  &mut bytes_written,
+                                                  &mut err)
+        };
+        if err.is_null() {
+            Ok(bytes_written as usize)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn write_all_future(&self, data: glib::Bytes, io_priority: gint)
+                            -> future::AsyncCall<usize>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let buffer = data.as_ptr() as gconstpointer;
+            let count = data.len() as gsize;
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_output_stream_write_all_async(self_raw,
+                                                         buffer,
+                                                         count,
+                                                         io_priority,
+                                                         cancellable,
+                                                         callback,
+                                                         user_data)
+                },
+                move |obj, res| {
+                    let _data = data;
+                    let s: &OutputStream = object::cast(obj);
+                    s.write_all_finish(res)
+                })
+        }
+    }
+
+    /// Copies the contents of `source` into this stream until
+    /// the end of `source` is reached.
+    ///
+    /// Returns the number of bytes spliced.
+    pub fn splice(&self,
+                  source: &InputStream,
+                  flags: OutputStreamSpliceFlags,
+                  cancellable: Option<&Cancellable>)
+                  -> result::Result<usize, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_output_stream_splice(self.as_mut_ptr(),
+                                        source.as_mut_ptr(),
+                                        flags.bits(),
+                                        cancellable_ptr(cancellable),
+                                        &mut err)
+        };
+        if err.is_null() {
+            Ok(ret as usize)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn splice_async<F>(&self,
+                           source: &InputStream,
+                           flags: OutputStreamSpliceFlags,
+                           io_priority: gint,
+                           cancellable: Option<&Cancellable>,
+                           callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_output_stream_splice_async(
+                    self_raw,
+                    source.as_mut_ptr(),
+                    flags.bits(),
+                    io_priority,
+                    cancellable_ptr(cancellable),
+                    Some(ready::async_ready_callback::<F>),
+                    callback);
+        }
+    }
+
+    pub fn splice_finish(&self, res: &AsyncResult)
+                         -> result::Result<usize, error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_output_stream_splice_finish(self.as_mut_ptr(),
+                                               res.as_mut_ptr(),
+                                               &mut err)
+        };
+        if err.is_null() {
+            Ok(ret as usize)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn splice_future(&self,
+                         source: &InputStream,
+                         flags: OutputStreamSpliceFlags,
+                         io_priority: gint)
+                         -> future::AsyncCall<usize>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let source_raw = source.as_mut_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_output_stream_splice_async(self_raw,
+                                                      source_raw,
+                                                      flags.bits(),
+                                                      io_priority,
+                                                      cancellable,
+                                                      callback,
+                                                      user_data)
+                },
+                |obj, res| {
+                    let s: &OutputStream = object::cast(obj);
+                    s.splice_finish(res)
+                })
+        }
+    }
+}
+
+impl IOStream {
+
+    /// Returns the input stream for reading from this stream.
+    pub fn get_input_stream(&self) -> &InputStream {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_io_stream_get_input_stream(self.as_mut_ptr());
+            wrap::from_raw(ret)
+        }
+    }
+
+    /// Returns the output stream for writing to this stream.
+    pub fn get_output_stream(&self) -> &OutputStream {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_io_stream_get_output_stream(self.as_mut_ptr());
+            wrap::from_raw(ret)
+        }
+    }
+
+    pub fn close(&self, cancellable: Option<&Cancellable>)
+                 -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_io_stream_close(self.as_mut_ptr(),
+                                   cancellable_ptr(cancellable),
+                                   &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+}
+
+impl Seekable {
+
+    /// Returns the current position within the stream.
+    pub fn tell(&self) -> i64 {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_seekable_tell(self.as_mut_ptr())
+        }
+    }
+
+    pub fn can_seek(&self) -> bool {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_seekable_can_seek(self.as_mut_ptr()) != FALSE
+        }
+    }
+
+    /// Moves the position within the stream by `offset`, relative to
+    /// the origin given by `seek_type`.
+    pub fn seek(&self,
+                offset: i64,
+                seek_type: glib::SeekType,
+                cancellable: Option<&Cancellable>)
+                -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_seekable_seek(self.as_mut_ptr(),
+                                 offset,
+                                 seek_type.to_int() as glib_ffi::GSeekType,
+                                 cancellable_ptr(cancellable),
+                                 &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn can_truncate(&self) -> bool {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_seekable_can_truncate(self.as_mut_ptr()) != FALSE
+        }
+    }
+
+    /// Sets the length of the stream to `offset`.
+    ///
+    /// The stream is extended with zero bytes if it is shorter.
+    pub fn truncate(&self, offset: i64, cancellable: Option<&Cancellable>)
+                    -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_seekable_truncate(self.as_mut_ptr(),
+                                     offset,
+                                     cancellable_ptr(cancellable),
+                                     &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+}
+
+impl MemoryInputStream {
+
+    pub fn new() -> refcount::Ref<MemoryInputStream> {
+        unsafe {
+            let ret = ffi::g_memory_input_stream_new();
+            refcount::Ref::from_raw(ret as *mut ffi::GMemoryInputStream)
+        }
+    }
+
+    /// Creates a stream reading the contents of `bytes`,
+    /// without copying the data.
+    pub fn from_bytes(bytes: &glib::Bytes)
+                      -> refcount::Ref<MemoryInputStream>
+    {
+        unsafe {
+            let ret = ffi::g_memory_input_stream_new_from_bytes(
+                            bytes.as_mut_ptr());
+            refcount::Ref::from_raw(ret as *mut ffi::GMemoryInputStream)
+        }
+    }
+
+    /// Appends `bytes` to the data to be read from the stream.
+    pub fn add_bytes(&self, bytes: &glib::Bytes) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_memory_input_stream_add_bytes(self.as_mut_ptr(),
+                                                 bytes.as_mut_ptr());
+        }
+    }
+}
+
+impl MemoryOutputStream {
+
+    /// Creates a stream writing to a buffer that grows as needed.
+    pub fn new_resizable() -> refcount::Ref<MemoryOutputStream> {
+        unsafe {
+            let ret = ffi::g_memory_output_stream_new_resizable();
+            refcount::Ref::from_raw(ret as *mut ffi::GMemoryOutputStream)
+        }
+    }
+
+    /// Returns the number of bytes written to the stream.
+    pub fn get_data_size(&self) -> usize {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_memory_output_stream_get_data_size(self.as_mut_ptr())
+                as usize
+        }
+    }
+
+    /// Takes the written data out of the stream without copying.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream has not been closed.
+    pub fn steal_as_bytes(&self) -> glib::Bytes {
+        use cast::AsOutputStream;
+        assert!(self.as_output_stream().is_closed(),
+                "MemoryOutputStream must be closed before stealing data");
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_memory_output_stream_steal_as_bytes(
+                            self.as_mut_ptr());
+            glib::Bytes::from_raw(ret)
+        }
+    }
+}
+
+impl FilterInputStream {
+
+    pub fn get_base_stream(&self) -> &InputStream {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_filter_input_stream_get_base_stream(
+                            self.as_mut_ptr());
+            wrap::from_raw(ret)
+        }
+    }
+
+    pub fn get_close_base_stream(&self) -> bool {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_filter_input_stream_get_close_base_stream(
+                    self.as_mut_ptr()) != FALSE
+        }
+    }
+
+    pub fn set_close_base_stream(&self, close_base: bool) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_filter_input_stream_set_close_base_stream(
+                    self.as_mut_ptr(), close_base as gboolean);
+        }
+    }
+}
+
+impl FilterOutputStream {
+
+    pub fn get_base_stream(&self) -> &OutputStream {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_filter_output_stream_get_base_stream(
+                            self.as_mut_ptr());
+            wrap::from_raw(ret)
+        }
+    }
+
+    pub fn get_close_base_stream(&self) -> bool {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_filter_output_stream_get_close_base_stream(
+                    self.as_mut_ptr()) != FALSE
+        }
+    }
+
+    pub fn set_close_base_stream(&self, close_base: bool) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_filter_output_stream_set_close_base_stream(
+                    self.as_mut_ptr(), close_base as gboolean);
+        }
+    }
+}
+
+impl BufferedInputStream {
+
+    pub fn new(base_stream: &InputStream)
+               -> refcount::Ref<BufferedInputStream>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_buffered_input_stream_new(
+                            base_stream.as_mut_ptr());
+            refcount::Ref::from_raw(ret as *mut ffi::GBufferedInputStream)
+        }
+    }
+
+    pub fn new_sized(base_stream: &InputStream, size: usize)
+                     -> refcount::Ref<BufferedInputStream>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_buffered_input_stream_new_sized(
+                            base_stream.as_mut_ptr(), size as gsize);
+            refcount::Ref::from_raw(ret as *mut ffi::GBufferedInputStream)
+        }
+    }
+
+    pub fn get_buffer_size(&self) -> usize {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_buffered_input_stream_get_buffer_size(self.as_mut_ptr())
+                as usize
+        }
+    }
+
+    /// Sets the size of the buffer. The buffer is never shrunk
+    /// below the size of the data it currently holds.
+    pub fn set_buffer_size(&self, size: usize) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_buffered_input_stream_set_buffer_size(self.as_mut_ptr(),
+                                                         size as gsize);
+        }
+    }
+
+    /// Returns the number of bytes currently held in the buffer.
+    pub fn get_available(&self) -> usize {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_buffered_input_stream_get_available(self.as_mut_ptr())
+                as usize
+        }
+    }
+
+    /// Reads up to `count` bytes from the base stream into the buffer,
+    /// or as many as the buffer can hold if `count` is `None`.
+    ///
+    /// Returns the number of bytes read, which is 0 at the end
+    /// of the base stream.
+    pub fn fill(&self, count: Option<usize>, cancellable: Option<&Cancellable>)
+                -> result::Result<usize, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_buffered_input_stream_fill(self.as_mut_ptr(),
+                                              fill_count(count),
+                                              cancellable_ptr(cancellable),
+                                              &mut err)
+        };
+        if err.is_null() {
+            Ok(ret as usize)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn fill_async<F>(&self,
+                         count: Option<usize>,
+                         io_priority: gint,
+                         cancellable: Option<&Cancellable>,
+                         callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_buffered_input_stream_fill_async(
+                    self_raw,
+                    fill_count(count),
+                    io_priority,
+                    cancellable_ptr(cancellable),
+                    Some(ready::async_ready_callback::<F>),
+                    callback);
+        }
+    }
+
+    pub fn fill_finish(&self, res: &AsyncResult)
+                      -> result::Result<usize, error::Error> {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_buffered_input_stream_fill_finish(self.as_mut_ptr(),
+                                                     res.as_mut_ptr(),
+                                                     &mut err)
+        };
+        if err.is_null() {
+            Ok(ret as usize)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn fill_future(&self, count: Option<usize>, io_priority: gint)
+                       -> future::AsyncCall<usize>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_buffered_input_stream_fill_async(self_raw,
+                                                            fill_count(count),
+                                                            io_priority,
+                                                            cancellable,
+                                                            callback,
+                                                            user_data)
+                },
+                |obj, res| {
+                    let s: &BufferedInputStream = object::cast(obj);
+                    s.fill_finish(res)
+                })
+        }
+    }
+
+    /// Copies buffered data starting at `offset` into `buffer`,
+    /// without consuming it. Returns the number of bytes copied.
+    pub fn peek(&self, buffer: &mut [u8], offset: usize) -> usize {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_buffered_input_stream_peek(self.as_mut_ptr(),
+                                              buffer.as_mut_ptr() as gpointer,
+                                              offset as gsize,
+                                              buffer.len() as gsize)
+                as usize
+        }
+    }
+}
+
+impl BufferedOutputStream {
+
+    pub fn new(base_stream: &OutputStream)
+               -> refcount::Ref<BufferedOutputStream>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_buffered_output_stream_new(
+                            base_stream.as_mut_ptr());
+            refcount::Ref::from_raw(ret as *mut ffi::GBufferedOutputStream)
+        }
+    }
+
+    pub fn new_sized(base_stream: &OutputStream, size: usize)
+                     -> refcount::Ref<BufferedOutputStream>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_buffered_output_stream_new_sized(
+                            base_stream.as_mut_ptr(), size as gsize);
+            refcount::Ref::from_raw(ret as *mut ffi::GBufferedOutputStream)
+        }
+    }
+
+    pub fn get_buffer_size(&self) -> usize {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_buffered_output_stream_get_buffer_size(self.as_mut_ptr())
+                as usize
+        }
+    }
+
+    pub fn set_buffer_size(&self, size: usize) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_buffered_output_stream_set_buffer_size(self.as_mut_ptr(),
+                                                          size as gsize);
+        }
+    }
+
+    pub fn get_auto_grow(&self) -> bool {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_buffered_output_stream_get_auto_grow(
+                    self.as_mut_ptr()) != FALSE
+        }
+    }
+
+    /// Sets whether the buffer grows to hold all written data
+    /// instead of being flushed to the base stream when full.
+    pub fn set_auto_grow(&self, auto_grow: bool) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_buffered_output_stream_set_auto_grow(
+                    self.as_mut_ptr(), auto_grow as gboolean);
+        }
+    }
+}
+
+impl DataInputStream {
+
+    pub fn new(base_stream: &InputStream)
+               -> refcount::Ref<DataInputStream>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_data_input_stream_new(base_stream.as_mut_ptr());
+            refcount::Ref::from_raw(ret)
+        }
+    }
+
+    pub fn get_byte_order(&self)
+                          -> result::Result<DataStreamByteOrder,
+                                            enumeration::UnknownValue>
+    {
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_get_byte_order(self.as_mut_ptr())
+        };
+        DataStreamByteOrder::from_int(ret as gint)
+    }
+
+    pub fn set_byte_order(&self, order: DataStreamByteOrder) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_set_byte_order(
+                    self.as_mut_ptr(),
+                    order.to_int() as ffi::GDataStreamByteOrder);
+        }
+    }
+
+    pub fn get_newline_type(&self)
+                            -> result::Result<DataStreamNewlineType,
+                                              enumeration::UnknownValue>
+    {
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_get_newline_type(self.as_mut_ptr())
+        };
+        DataStreamNewlineType::from_int(ret as gint)
+    }
+
+    pub fn set_newline_type(&self, newline_type: DataStreamNewlineType) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_set_newline_type(
+                    self.as_mut_ptr(),
+                    newline_type.to_int() as ffi::GDataStreamNewlineType);
+        }
+    }
+
+    pub fn read_byte(&self, cancellable: Option<&Cancellable>)
+                     -> result::Result<u8, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_read_byte(self.as_mut_ptr(),
+                                               cancellable_ptr(cancellable),
+                                               &mut err)
+        };
+        if err.is_null() {
+            Ok(ret)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn read_int16(&self, cancellable: Option<&Cancellable>)
+                      -> result::Result<i16, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_read_int16(self.as_mut_ptr(),
+                                                cancellable_ptr(cancellable),
+                                                &mut err)
+        };
+        if err.is_null() {
+            Ok(ret)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn read_uint16(&self, cancellable: Option<&Cancellable>)
+                       -> result::Result<u16, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_read_uint16(self.as_mut_ptr(),
+                                                 cancellable_ptr(cancellable),
+                                                 &mut err)
+        };
+        if err.is_null() {
+            Ok(ret)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn read_int32(&self, cancellable: Option<&Cancellable>)
+                      -> result::Result<i32, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_read_int32(self.as_mut_ptr(),
+                                                cancellable_ptr(cancellable),
+                                                &mut err)
+        };
+        if err.is_null() {
+            Ok(ret)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn read_uint32(&self, cancellable: Option<&Cancellable>)
+                       -> result::Result<u32, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_read_uint32(self.as_mut_ptr(),
+                                                 cancellable_ptr(cancellable),
+                                                 &mut err)
+        };
+        if err.is_null() {
+            Ok(ret)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn read_int64(&self, cancellable: Option<&Cancellable>)
+                      -> result::Result<i64, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_read_int64(self.as_mut_ptr(),
+                                                cancellable_ptr(cancellable),
+                                                &mut err)
+        };
+        if err.is_null() {
+            Ok(ret)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn read_uint64(&self, cancellable: Option<&Cancellable>)
+                       -> result::Result<u64, error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_read_uint64(self.as_mut_ptr(),
+                                                 cancellable_ptr(cancellable),
+                                                 &mut err)
+        };
+        if err.is_null() {
+            Ok(ret)
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    /// Reads a line, not including the line terminator.
+    ///
+    /// Returns `None` at the end of the stream. The line is returned
+    /// as bytes, because the stream data need not be valid UTF-8.
+    pub fn read_line(&self, cancellable: Option<&Cancellable>)
+                     -> result::Result<Option<Vec<u8>>, error::Error>
+    {
+        let mut length: gsize = 0;
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_read_line(self.as_mut_ptr(),
+                                               &mut length,
+                                               cancellable_ptr(cancellable),
+                                               &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { take_data(ret, length) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    /// Reads data up to, but not including, any of the bytes
+    /// in `stop_chars`. The stop byte is left in the stream.
+    ///
+    /// Returns `None` at the end of the stream.
+    pub fn read_upto(&self,
+                     stop_chars: &[u8],
+                     cancellable: Option<&Cancellable>)
+                    -> result::Result<Option<Vec<u8>>, error::Error>
+    {
+        let mut length: gsize = 0;
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_read_upto(self.as_mut_ptr(),
+                                               stop_chars.as_ptr()
+                                                   as *const gchar,
+                                               stop_chars.len() as gssize,
+                                               &mut length,
+                                               cancellable_ptr(cancellable),
+                                               &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { take_data(ret, length) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn read_line_async<F>(&self,
+                              io_priority: gint,
+                              cancellable: Option<&Cancellable>,
+                              callback: F)
+        where F: FnOnce(&gobject::Object, &AsyncResult),
+              F: Send + 'static
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let callback: gpointer = mem::transmute(Box::new(callback));
+
+            ffi::g_data_input_stream_read_line_async(
+                    self_raw,
+                    io_priority,
+                    cancellable_ptr(cancellable),
+                    Some(ready::async_ready_callback::<F>),
+                    callback);
+        }
+    }
+
+    pub fn read_line_finish(&self, res: &AsyncResult)
+                           -> result::Result<Option<Vec<u8>>,
+                                             error::Error> {
+        let mut length: gsize = 0;
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_input_stream_read_line_finish(self.as_mut_ptr(),
+                                                      res.as_mut_ptr(),
+                                                      &mut length,
+                                                      &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { take_data(ret, length) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn read_line_future(&self, io_priority: gint)
+                            -> future::AsyncCall<Option<Vec<u8>>>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            future::AsyncCall::new(
+                |cancellable, callback, user_data| {
+                    ffi::g_data_input_stream_read_line_async(self_raw,
+                                                             io_priority,
+                                                             cancellable,
+                                                             callback,
+                                                             user_data)
+                },
+                |obj, res| {
+                    let s: &DataInputStream = object::cast(obj);
+                    s.read_line_finish(res)
+                })
+        }
+    }
+
+    /// Returns an iterator over the remaining lines, read
+    /// synchronously with `read_line()`.
+    pub fn lines(&self) -> Lines {
+        Lines { stream: self, done: false }
+    }
+
+    /// Returns a stream yielding the remaining lines,
+    /// read asynchronously with `read_line_async()`.
+    pub fn lines_stream(&self, io_priority: gint) -> future::LineStream {
+        future::LineStream::new(self, io_priority)
+    }
+}
+
+/// An iterator over the lines of a `DataInputStream`.
+///
+/// The iteration stops after the first error.
+pub struct Lines<'a> {
+    stream: &'a DataInputStream,
+    done: bool,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = result::Result<Vec<u8>, error::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.stream.read_line(None) {
+            Ok(Some(line)) => Some(Ok(line)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl DataOutputStream {
+
+    pub fn new(base_stream: &OutputStream)
+               -> refcount::Ref<DataOutputStream>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_data_output_stream_new(base_stream.as_mut_ptr());
+            refcount::Ref::from_raw(ret)
+        }
+    }
+
+    pub fn get_byte_order(&self)
+                          -> result::Result<DataStreamByteOrder,
+                                            enumeration::UnknownValue>
+    {
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_output_stream_get_byte_order(self.as_mut_ptr())
+        };
+        DataStreamByteOrder::from_int(ret as gint)
+    }
+
+    pub fn set_byte_order(&self, order: DataStreamByteOrder) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_output_stream_set_byte_order(
+                    self.as_mut_ptr(),
+                    order.to_int() as ffi::GDataStreamByteOrder);
+        }
+    }
+
+    pub fn put_byte(&self, data: u8, cancellable: Option<&Cancellable>)
+                    -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_output_stream_put_byte(self.as_mut_ptr(),
+                                               data,
+                                               cancellable_ptr(cancellable),
+                                               &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn put_int16(&self, data: i16, cancellable: Option<&Cancellable>)
+                     -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_output_stream_put_int16(self.as_mut_ptr(),
+                                                data,
+                                                cancellable_ptr(cancellable),
+                                                &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn put_uint16(&self, data: u16, cancellable: Option<&Cancellable>)
+                      -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_output_stream_put_uint16(self.as_mut_ptr(),
+                                                 data,
+                                                 cancellable_ptr(cancellable),
+                                                 &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn put_int32(&self, data: i32, cancellable: Option<&Cancellable>)
+                     -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_output_stream_put_int32(self.as_mut_ptr(),
+                                                data,
+                                                cancellable_ptr(cancellable),
+                                                &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn put_uint32(&self, data: u32, cancellable: Option<&Cancellable>)
+                      -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_output_stream_put_uint32(self.as_mut_ptr(),
+                                                 data,
+                                                 cancellable_ptr(cancellable),
+                                                 &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn put_int64(&self, data: i64, cancellable: Option<&Cancellable>)
+                     -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_output_stream_put_int64(self.as_mut_ptr(),
+                                                data,
+                                                cancellable_ptr(cancellable),
+                                                &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    pub fn put_uint64(&self, data: u64, cancellable: Option<&Cancellable>)
+                      -> result::Result<(), error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_data_output_stream_put_uint64(self.as_mut_ptr(),
+                                                 data,
+                                                 cancellable_ptr(cancellable),
+                                                 &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    /// Writes the bytes of `s`, without a terminating NUL.
+    pub fn put_string(&self, s: &str, cancellable: Option<&Cancellable>)
+                      -> result::Result<(), error::Error>
+    {
+        let mut bytes_written: gsize = 0;
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let _: gboolean = unsafe {
+            use grust::wrap::Wrapper;
+            // g_data_output_stream_put_string() requires a NUL-terminated
+            // string, so the bytes are written to the stream directly
+            let stream = self.as_mut_ptr() as *mut ffi::GOutputStream;
+            ffi::g_output_stream_write_all(stream,
+                                           s.as_ptr() as gconstpointer,
+                                           s.len() as gsize,
+                                           &mut bytes_written,
+                                           cancellable_ptr(cancellable),
+                                           &mut err)
+        };
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+}
+
+impl Converter {
+
+    /// Converts data from `inbuf` into `outbuf`.
+    ///
+    /// Returns the conversion result, the number of bytes consumed
+    /// from `inbuf` and the number of bytes written to `outbuf`.
+    pub fn convert(&self,
+                   inbuf: &[u8],
+                   outbuf: &mut [u8],
+                   flags: ConverterFlags)
+                  -> result::Result<(ConverterResult, usize, usize),
+                                    error::Error>
+    {
+        let mut bytes_read: gsize = 0;
+        let mut bytes_written: gsize = 0;
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_converter_convert(self.as_mut_ptr(),
+                                     inbuf.as_ptr() as gconstpointer,
+                                     inbuf.len() as gsize,
+                                     outbuf.as_mut_ptr() as gpointer,
+                                     outbuf.len() as gsize,
+                                     flags.bits(),
+                                     &mut bytes_read,
+                                     &mut bytes_written,
+                                     &mut err)
+        };
+        if !err.is_null() {
+            return Err(unsafe { error::Error::from_raw(err) });
+        }
+        let res = ConverterResult::from_int(ret as gint)
+                  .unwrap_or(ConverterResult::Error);
+        Ok((res, bytes_read as usize, bytes_written as usize))
+    }
+
+    /// Resets the converter to its initial state.
+    pub fn reset(&self) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_converter_reset(self.as_mut_ptr());
+        }
+    }
+}
+
+impl ZlibCompressor {
+
+    /// Creates a compressor. The `level` ranges from 0 for
+    /// no compression to 9 for most compression; -1 selects
+    /// the default level.
+    pub fn new(format: ZlibCompressorFormat, level: gint)
+               -> refcount::Ref<ZlibCompressor>
+    {
+        unsafe {
+            let ret = ffi::g_zlib_compressor_new(
+                            format.to_int() as ffi::GZlibCompressorFormat,
+                            level);
+            refcount::Ref::from_raw(ret)
+        }
+    }
+}
+
+impl ZlibDecompressor {
+
+    pub fn new(format: ZlibCompressorFormat)
+               -> refcount::Ref<ZlibDecompressor>
+    {
+        unsafe {
+            let ret = ffi::g_zlib_decompressor_new(
+                            format.to_int() as ffi::GZlibCompressorFormat);
+            refcount::Ref::from_raw(ret)
+        }
+    }
+}
+
+impl CharsetConverter {
+
+    /// Creates a converter between two character sets.
+    ///
+    /// Fails if the conversion is not supported.
+    pub fn new(to_charset: &gstr::Utf8, from_charset: &gstr::Utf8)
+               -> result::Result<refcount::Ref<CharsetConverter>,
+                                 error::Error>
+    {
+        let mut err: *mut glib_ffi::GError = ptr::null_mut();
+        let ret = unsafe {
+            ffi::g_charset_converter_new(to_charset.as_ptr(),
+                                         from_charset.as_ptr(),
+                                         &mut err)
+        };
+        if err.is_null() {
+            Ok(unsafe { refcount::Ref::from_raw(ret) })
+        } else {
+            Err(unsafe { error::Error::from_raw(err) })
+        }
+    }
+
+    /// Sets whether invalid input is replaced by fallback
+    /// characters rather than causing an error.
+    pub fn set_use_fallback(&self, use_fallback: bool) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_charset_converter_set_use_fallback(
+                    self.as_mut_ptr(), use_fallback as gboolean);
+        }
+    }
+
+    pub fn get_use_fallback(&self) -> bool {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_charset_converter_get_use_fallback(
+                    self.as_mut_ptr()) != FALSE
+        }
+    }
+
+    /// Returns the number of fallback replacements made so far.
+    pub fn get_num_fallbacks(&self) -> u32 {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_charset_converter_get_num_fallbacks(self.as_mut_ptr())
+                as u32
+        }
+    }
+}
+
+impl ConverterInputStream {
+
+    /// Creates a stream reading the data of `base_stream`
+    /// converted by `converter`.
+    pub fn new(base_stream: &InputStream, converter: &Converter)
+               -> refcount::Ref<ConverterInputStream>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_converter_input_stream_new(
+                            base_stream.as_mut_ptr(),
+                            converter.as_mut_ptr());
+            refcount::Ref::from_raw(ret as *mut ffi::GConverterInputStream)
+        }
+    }
+
+    pub fn get_converter(&self) -> &Converter {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_converter_input_stream_get_converter(
+                            self.as_mut_ptr());
+            wrap::from_raw(ret)
+        }
+    }
+}
+
+impl ConverterOutputStream {
+
+    /// Creates a stream converting the written data with `converter`
+    /// and writing the result to `base_stream`.
+    pub fn new(base_stream: &OutputStream, converter: &Converter)
+               -> refcount::Ref<ConverterOutputStream>
+    {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_converter_output_stream_new(
+                            base_stream.as_mut_ptr(),
+                            converter.as_mut_ptr());
+            refcount::Ref::from_raw(ret as *mut ffi::GConverterOutputStream)
+        }
+    }
+
+    pub fn get_converter(&self) -> &Converter {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_converter_output_stream_get_converter(
+                            self.as_mut_ptr());
+            wrap::from_raw(ret)
+        }
+    }
+}
+
+/// Value of a file attribute, as returned by `FileInfo::get_attribute`.
+#[derive(Debug)]
+pub enum FileAttributeValue<'a> {
+    /// The attribute is not set, or its type is not known.
+    Invalid,
+    String(&'a str),
+    ByteString(&'a [u8]),
+    Boolean(bool),
+    Uint32(u32),
+    Int32(i32),
+    Uint64(u64),
+    Int64(i64),
+    Object(&'a gobject::Object),
+    Stringv(Vec<&'a str>),
+    /// A string list with entries that are not valid UTF-8.
+    ByteStringv(Vec<&'a [u8]>),
+}
+
+unsafe fn borrow_str<'a>(ptr: *const gchar) -> Option<&'a str> {
+    if ptr.is_null() {
+        None
+    } else {
+        str::from_utf8(CStr::from_ptr(ptr).to_bytes()).ok()
+    }
+}
+
+impl FileInfo {
+
+    pub fn new() -> refcount::Ref<FileInfo> {
+        unsafe { refcount::Ref::from_raw(ffi::g_file_info_new()) }
+    }
+
+    pub fn has_attribute(&self, attribute: &gstr::Utf8) -> bool {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_info_has_attribute(self.as_mut_ptr(),
+                                           attribute.as_ptr()) != FALSE
+        }
+    }
+
+    pub fn get_attribute_type(&self, attribute: &gstr::Utf8)
+                              -> result::Result<FileAttributeType,
+                                                enumeration::UnknownValue>
+    {
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_info_get_attribute_type(self.as_mut_ptr(),
+                                                attribute.as_ptr())
+        };
+        FileAttributeType::from_int(ret as gint)
+    }
+
+    /// Returns the value of an attribute, typed according to
+    /// `get_attribute_type()`.
+    ///
+    /// Attributes that are not set, or have a type unknown to
+    /// these bindings, are returned as `FileAttributeValue::Invalid`.
+    /// String values that are not valid UTF-8 are returned as
+    /// `ByteString`, and string lists with any such entries
+    /// as `ByteStringv`.
+    pub fn get_attribute<'a>(&'a self, attribute: &gstr::Utf8)
+                             -> FileAttributeValue<'a>
+    {
+        let attr_type = match self.get_attribute_type(attribute) {
+            Ok(t)  => t,
+            Err(_) => return FileAttributeValue::Invalid
+        };
+        unsafe {
+            use grust::wrap::Wrapper;
+            let self_raw = self.as_mut_ptr();
+            let attr_raw = attribute.as_ptr();
+            match attr_type {
+                FileAttributeType::Invalid => FileAttributeValue::Invalid,
+                FileAttributeType::String => {
+                    let ret = ffi::g_file_info_get_attribute_string(
+                                    self_raw, attr_raw);
+                    if ret.is_null() {
+                        return FileAttributeValue::Invalid;
+                    }
+                    let bytes = CStr::from_ptr(ret).to_bytes();
+                    match str::from_utf8(bytes) {
+                        Ok(s)  => FileAttributeValue::String(s),
+                        Err(_) => FileAttributeValue::ByteString(bytes)
+                    }
+                }
+                FileAttributeType::ByteString => {
+                    let ret = ffi::g_file_info_get_attribute_byte_string(
+                                    self_raw, attr_raw);
+                    if ret.is_null() {
+                        FileAttributeValue::Invalid
+                    } else {
+                        let bytes = CStr::from_ptr(ret).to_bytes();
+                        FileAttributeValue::ByteString(bytes)
+                    }
+                }
+                FileAttributeType::Boolean => {
+                    let ret = ffi::g_file_info_get_attribute_boolean(
+                                    self_raw, attr_raw);
+                    FileAttributeValue::Boolean(ret != FALSE)
+                }
+                FileAttributeType::Uint32 => {
+                    let ret = ffi::g_file_info_get_attribute_uint32(
+                                    self_raw, attr_raw);
+                    FileAttributeValue::Uint32(ret as u32)
+                }
+                FileAttributeType::Int32 => {
+                    let ret = ffi::g_file_info_get_attribute_int32(
+                                    self_raw, attr_raw);
+                    FileAttributeValue::Int32(ret as i32)
+                }
+                FileAttributeType::Uint64 => {
+                    let ret = ffi::g_file_info_get_attribute_uint64(
+                                    self_raw, attr_raw);
+                    FileAttributeValue::Uint64(ret as u64)
+                }
+                FileAttributeType::Int64 => {
+                    let ret = ffi::g_file_info_get_attribute_int64(
+                                    self_raw, attr_raw);
+                    FileAttributeValue::Int64(ret as i64)
+                }
+                FileAttributeType::Object => {
+                    let ret = ffi::g_file_info_get_attribute_object(
+                                    self_raw, attr_raw);
+                    if ret.is_null() {
+                        FileAttributeValue::Invalid
+                    } else {
+                        FileAttributeValue::Object(wrap::from_raw(ret))
+                    }
+                }
+                FileAttributeType::Stringv => {
+                    let mut p = ffi::g_file_info_get_attribute_stringv(
+                                    self_raw, attr_raw);
+                    let mut entries = Vec::new();
+                    if !p.is_null() {
+                        while !(*p).is_null() {
+                            entries.push(CStr::from_ptr(*p).to_bytes());
+                            p = p.offset(1);
+                        }
+                    }
+                    let strv: Option<Vec<&str>> = entries.iter()
+                        .map(|b| str::from_utf8(b).ok())
+                        .collect();
+                    match strv {
+                        Some(strv) => FileAttributeValue::Stringv(strv),
+                        None => FileAttributeValue::ByteStringv(entries)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sets a string attribute. GIO does not require the value
+    /// to be valid UTF-8.
+    pub fn set_attribute_string(&self, attribute: &gstr::Utf8, value: &CStr) {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_info_set_attribute_string(self.as_mut_ptr(),
+                                                  attribute.as_ptr(),
+                                                  value.as_ptr());
+        }
+    }
+
+    /// Sets a string list attribute. GIO does not require the values
+    /// to be valid UTF-8.
+    pub fn set_attribute_stringv(&self,
+                                 attribute: &gstr::Utf8,
+                                 value: &[&CStr])
+    {
+        // The strings are copied by GIO
+        let mut strv: Vec<*mut gchar> = value.iter()
+            .map(|s| s.as_ptr() as *mut gchar)
+            .collect();
+        strv.push(ptr::null_mut());
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_info_set_attribute_stringv(self.as_mut_ptr(),
+                                                   attribute.as_ptr(),
+                                                   strv.as_mut_ptr());
+        }
+    }
+
+    pub fn get_size(&self) -> i64 {
+        unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_info_get_size(self.as_mut_ptr()) as i64
+        }
+    }
+
+    pub fn get_file_type(&self)
+                         -> result::Result<FileType, enumeration::UnknownValue>
+    {
+        let ret = unsafe {
+            use grust::wrap::Wrapper;
+            ffi::g_file_info_get_file_type(self.as_mut_ptr())
+        };
+        FileType::from_int(ret as gint)
+    }
+
+    pub fn get_content_type(&self) -> Option<&str> {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_file_info_get_content_type(self.as_mut_ptr());
+            borrow_str(ret)
+        }
+    }
+
+    pub fn get_display_name(&self) -> Option<&str> {
+        unsafe {
+            use grust::wrap::Wrapper;
+            let ret = ffi::g_file_info_get_display_name(self.as_mut_ptr());
+            borrow_str(ret)
+        }
+    }
+
+    /// Returns the modification time, if the `time::modified`
+    /// attribute has been queried.
+    ///
+    /// The sub-second part is filled in from `time::modified-usec`
+    /// when that attribute is also present.
+    pub fn get_modification_time(&self) -> Option<SystemTime> {
+        let secs = match self.get_attribute(g_utf8!("time::modified")) {
+            FileAttributeValue::Uint64(v) => v,
+            _ => return None
+        };
+        let usecs = match self.get_attribute(g_utf8!("time::modified-usec")) {
+            FileAttributeValue::Uint32(v) => v,
+            _ => 0
+        };
+        Some(UNIX_EPOCH + Duration::new(secs, usecs * 1000))
+    }
+
+    /// Returns the UNIX file mode, if the `unix::mode` attribute
+    /// has been queried.
+    pub fn get_unix_mode(&self) -> Option<u32> {
+        match self.get_attribute(g_utf8!("unix::mode")) {
+            FileAttributeValue::Uint32(v) => Some(v),
+            _ => None
+        }
+    }
+}
+
+unsafe impl object::ObjectType for AsyncResult {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_async_result_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for File {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_file_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for Cancellable {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_cancellable_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for InputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_input_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for FileInputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_file_input_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for OutputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_output_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for FileOutputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_file_output_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for FileInfo {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_file_info_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for FileEnumerator {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_file_enumerator_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for FileMonitor {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_file_monitor_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for IOStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_io_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for FileIOStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_file_io_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for MemoryInputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_memory_input_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for MemoryOutputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_memory_output_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for FilterInputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_filter_input_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for BufferedInputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_buffered_input_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for DataInputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_data_input_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for FilterOutputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_filter_output_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for BufferedOutputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_buffered_output_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for DataOutputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_data_output_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for Converter {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_converter_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for ZlibCompressor {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_zlib_compressor_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for ZlibDecompressor {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_zlib_decompressor_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for CharsetConverter {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_charset_converter_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for ConverterInputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_converter_input_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for ConverterOutputStream {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_converter_output_stream_get_type())
+        }
+    }
+}
+
+unsafe impl object::ObjectType for Seekable {
+    fn get_type() -> GType {
+        unsafe {
+            GType::from_raw(ffi::g_seekable_get_type())
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for Cancellable {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for InputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<InputStream> for FileInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &InputStream {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for FileInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        use cast::AsInputStream;
+        self.as_input_stream().upcast()
+    }
+}
+
+impl object::Upcast<Seekable> for FileInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &Seekable {
+        unsafe {
+            wrap::from_raw(self as *const FileInputStream as *const ffi::GSeekable)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for OutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<OutputStream> for FileOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &OutputStream {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for FileOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        use cast::AsOutputStream;
+        self.as_output_stream().upcast()
+    }
+}
+
+impl object::Upcast<Seekable> for FileOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &Seekable {
+        unsafe {
+            wrap::from_raw(self as *const FileOutputStream as *const ffi::GSeekable)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for FileInfo {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        unsafe {
+            wrap::from_raw(self as *const FileInfo as *const gobject_ffi::GObject)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for FileEnumerator {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for FileMonitor {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for IOStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<IOStream> for FileIOStream {
+
+    #[inline]
+    fn upcast(&self) -> &IOStream {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for FileIOStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        use cast::AsIOStream;
+        self.as_io_stream().upcast()
+    }
+}
+
+impl object::Upcast<Seekable> for FileIOStream {
+
+    #[inline]
+    fn upcast(&self) -> &Seekable {
+        unsafe {
+            wrap::from_raw(self as *const FileIOStream as *const ffi::GSeekable)
+        }
+    }
+}
+
+impl object::Upcast<InputStream> for MemoryInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &InputStream {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for MemoryInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        use cast::AsInputStream;
+        self.as_input_stream().upcast()
+    }
+}
+
+impl object::Upcast<Seekable> for MemoryInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &Seekable {
+        unsafe {
+            wrap::from_raw(self as *const MemoryInputStream as *const ffi::GSeekable)
+        }
+    }
+}
+
+impl object::Upcast<OutputStream> for MemoryOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &OutputStream {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for MemoryOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        use cast::AsOutputStream;
+        self.as_output_stream().upcast()
+    }
+}
+
+impl object::Upcast<Seekable> for MemoryOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &Seekable {
+        unsafe {
+            wrap::from_raw(self as *const MemoryOutputStream as *const ffi::GSeekable)
+        }
+    }
+}
+
+impl object::Upcast<InputStream> for FilterInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &InputStream {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for FilterInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        use cast::AsInputStream;
+        self.as_input_stream().upcast()
+    }
+}
+
+impl object::Upcast<FilterInputStream> for BufferedInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &FilterInputStream {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<InputStream> for BufferedInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &InputStream {
+        use cast::AsFilterInputStream;
+        self.as_filter_input_stream().upcast()
+    }
+}
+
+impl object::Upcast<gobject::Object> for BufferedInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        use cast::AsFilterInputStream;
+        self.as_filter_input_stream().upcast()
+    }
+}
+
+impl object::Upcast<Seekable> for BufferedInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &Seekable {
+        unsafe {
+            wrap::from_raw(self as *const BufferedInputStream as *const ffi::GSeekable)
+        }
+    }
+}
+
+impl object::Upcast<BufferedInputStream> for DataInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &BufferedInputStream {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<FilterInputStream> for DataInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &FilterInputStream {
+        use cast::AsBufferedInputStream;
+        self.as_buffered_input_stream().upcast()
+    }
+}
+
+impl object::Upcast<InputStream> for DataInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &InputStream {
+        use cast::AsBufferedInputStream;
+        self.as_buffered_input_stream().upcast()
+    }
+}
+
+impl object::Upcast<gobject::Object> for DataInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        use cast::AsBufferedInputStream;
+        self.as_buffered_input_stream().upcast()
+    }
+}
+
+impl object::Upcast<Seekable> for DataInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &Seekable {
+        unsafe {
+            wrap::from_raw(self as *const DataInputStream as *const ffi::GSeekable)
+        }
+    }
+}
+
+impl object::Upcast<OutputStream> for FilterOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &OutputStream {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for FilterOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        use cast::AsOutputStream;
+        self.as_output_stream().upcast()
+    }
+}
+
+impl object::Upcast<FilterOutputStream> for BufferedOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &FilterOutputStream {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<OutputStream> for BufferedOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &OutputStream {
+        use cast::AsFilterOutputStream;
+        self.as_filter_output_stream().upcast()
+    }
+}
+
+impl object::Upcast<gobject::Object> for BufferedOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        use cast::AsFilterOutputStream;
+        self.as_filter_output_stream().upcast()
+    }
+}
+
+impl object::Upcast<Seekable> for BufferedOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &Seekable {
+        unsafe {
+            wrap::from_raw(self as *const BufferedOutputStream as *const ffi::GSeekable)
+        }
+    }
+}
+
+impl object::Upcast<FilterOutputStream> for DataOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &FilterOutputStream {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<OutputStream> for DataOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &OutputStream {
+        use cast::AsFilterOutputStream;
+        self.as_filter_output_stream().upcast()
+    }
+}
+
+impl object::Upcast<gobject::Object> for DataOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        use cast::AsFilterOutputStream;
+        self.as_filter_output_stream().upcast()
+    }
+}
+
+impl object::Upcast<Seekable> for DataOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &Seekable {
+        unsafe {
+            wrap::from_raw(self as *const DataOutputStream as *const ffi::GSeekable)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for ZlibCompressor {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        unsafe {
+            wrap::from_raw(self as *const ZlibCompressor as *const gobject_ffi::GObject)
+        }
+    }
+}
+
+impl object::Upcast<Converter> for ZlibCompressor {
+
+    #[inline]
+    fn upcast(&self) -> &Converter {
+        unsafe {
+            wrap::from_raw(self as *const ZlibCompressor as *const ffi::GConverter)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for ZlibDecompressor {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        unsafe {
+            wrap::from_raw(self as *const ZlibDecompressor as *const gobject_ffi::GObject)
+        }
+    }
+}
+
+impl object::Upcast<Converter> for ZlibDecompressor {
+
+    #[inline]
+    fn upcast(&self) -> &Converter {
+        unsafe {
+            wrap::from_raw(self as *const ZlibDecompressor as *const ffi::GConverter)
+        }
+    }
+}
+
+impl object::Upcast<gobject::Object> for CharsetConverter {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        unsafe {
+            wrap::from_raw(self as *const CharsetConverter as *const gobject_ffi::GObject)
+        }
+    }
+}
+
+impl object::Upcast<Converter> for CharsetConverter {
+
+    #[inline]
+    fn upcast(&self) -> &Converter {
+        unsafe {
+            wrap::from_raw(self as *const CharsetConverter as *const ffi::GConverter)
+        }
+    }
+}
+
+impl object::Upcast<FilterInputStream> for ConverterInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &FilterInputStream {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<InputStream> for ConverterInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &InputStream {
+        use cast::AsFilterInputStream;
+        self.as_filter_input_stream().upcast()
+    }
+}
+
+impl object::Upcast<gobject::Object> for ConverterInputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        use cast::AsFilterInputStream;
+        self.as_filter_input_stream().upcast()
+    }
+}
+
+impl object::Upcast<FilterOutputStream> for ConverterOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &FilterOutputStream {
+        unsafe {
+            wrap::from_raw(&self.raw.parent_instance)
+        }
+    }
+}
+
+impl object::Upcast<OutputStream> for ConverterOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &OutputStream {
+        use cast::AsFilterOutputStream;
+        self.as_filter_output_stream().upcast()
+    }
+}
+
+impl object::Upcast<gobject::Object> for ConverterOutputStream {
+
+    #[inline]
+    fn upcast(&self) -> &gobject::Object {
+        use cast::AsFilterOutputStream;
+        self.as_filter_output_stream().upcast()
+    }
+}
+
+impl AsRef<AsyncResult> for AsyncResult {
+
+    #[inline]
+    fn as_ref(&self) -> &AsyncResult {
+        self
+    }
+}
+
+impl AsRef<File> for File {
+
+    #[inline]
+    fn as_ref(&self) -> &File {
+        self
+    }
+}
+
+impl AsRef<Cancellable> for Cancellable {
+
+    #[inline]
+    fn as_ref(&self) -> &Cancellable {
+        self
+    }
+}
+
+impl AsRef<gobject::Object> for Cancellable {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<InputStream> for InputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &InputStream {
+        self
+    }
+}
+
+impl AsRef<gobject::Object> for InputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<FileInputStream> for FileInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &FileInputStream {
+        self
+    }
+}
+
+impl AsRef<InputStream> for FileInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &InputStream {
+        use cast::AsInputStream;
+        self.as_input_stream()
+    }
+}
+
+impl AsRef<gobject::Object> for FileInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<Seekable> for FileInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &Seekable {
+        use cast::AsSeekable;
+        self.as_seekable()
+    }
+}
+
+impl AsRef<OutputStream> for OutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &OutputStream {
+        self
+    }
+}
+
+impl AsRef<gobject::Object> for OutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<FileOutputStream> for FileOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &FileOutputStream {
+        self
+    }
+}
+
+impl AsRef<OutputStream> for FileOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &OutputStream {
+        use cast::AsOutputStream;
+        self.as_output_stream()
+    }
+}
+
+impl AsRef<gobject::Object> for FileOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<Seekable> for FileOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &Seekable {
+        use cast::AsSeekable;
+        self.as_seekable()
+    }
+}
+
+impl AsRef<FileInfo> for FileInfo {
+
+    #[inline]
+    fn as_ref(&self) -> &FileInfo {
+        self
+    }
+}
+
+impl AsRef<gobject::Object> for FileInfo {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<FileEnumerator> for FileEnumerator {
+
+    #[inline]
+    fn as_ref(&self) -> &FileEnumerator {
+        self
+    }
+}
+
+impl AsRef<gobject::Object> for FileEnumerator {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<FileMonitor> for FileMonitor {
+
+    #[inline]
+    fn as_ref(&self) -> &FileMonitor {
+        self
+    }
+}
+
+impl AsRef<gobject::Object> for FileMonitor {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<IOStream> for IOStream {
+
+    #[inline]
+    fn as_ref(&self) -> &IOStream {
+        self
+    }
+}
+
+impl AsRef<gobject::Object> for IOStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<FileIOStream> for FileIOStream {
+
+    #[inline]
+    fn as_ref(&self) -> &FileIOStream {
+        self
+    }
+}
+
+impl AsRef<IOStream> for FileIOStream {
+
+    #[inline]
+    fn as_ref(&self) -> &IOStream {
+        use cast::AsIOStream;
+        self.as_io_stream()
+    }
+}
+
+impl AsRef<gobject::Object> for FileIOStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<Seekable> for FileIOStream {
+
+    #[inline]
+    fn as_ref(&self) -> &Seekable {
+        use cast::AsSeekable;
+        self.as_seekable()
+    }
+}
+
+impl AsRef<MemoryInputStream> for MemoryInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &MemoryInputStream {
+        self
+    }
+}
+
+impl AsRef<InputStream> for MemoryInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &InputStream {
+        use cast::AsInputStream;
+        self.as_input_stream()
+    }
+}
+
+impl AsRef<gobject::Object> for MemoryInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<Seekable> for MemoryInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &Seekable {
+        use cast::AsSeekable;
+        self.as_seekable()
+    }
+}
+
+impl AsRef<MemoryOutputStream> for MemoryOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &MemoryOutputStream {
+        self
+    }
+}
+
+impl AsRef<OutputStream> for MemoryOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &OutputStream {
+        use cast::AsOutputStream;
+        self.as_output_stream()
+    }
+}
+
+impl AsRef<gobject::Object> for MemoryOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<Seekable> for MemoryOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &Seekable {
+        use cast::AsSeekable;
+        self.as_seekable()
+    }
+}
+
+impl AsRef<FilterInputStream> for FilterInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &FilterInputStream {
+        self
+    }
+}
+
+impl AsRef<InputStream> for FilterInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &InputStream {
+        use cast::AsInputStream;
+        self.as_input_stream()
+    }
+}
+
+impl AsRef<gobject::Object> for FilterInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<BufferedInputStream> for BufferedInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &BufferedInputStream {
+        self
+    }
+}
+
+impl AsRef<FilterInputStream> for BufferedInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &FilterInputStream {
+        use cast::AsFilterInputStream;
+        self.as_filter_input_stream()
+    }
+}
+
+impl AsRef<InputStream> for BufferedInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &InputStream {
+        use cast::AsInputStream;
+        self.as_input_stream()
+    }
+}
+
+impl AsRef<gobject::Object> for BufferedInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<Seekable> for BufferedInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &Seekable {
+        use cast::AsSeekable;
+        self.as_seekable()
+    }
+}
+
+impl AsRef<DataInputStream> for DataInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &DataInputStream {
+        self
+    }
+}
+
+impl AsRef<BufferedInputStream> for DataInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &BufferedInputStream {
+        use cast::AsBufferedInputStream;
+        self.as_buffered_input_stream()
+    }
+}
+
+impl AsRef<FilterInputStream> for DataInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &FilterInputStream {
+        use cast::AsFilterInputStream;
+        self.as_filter_input_stream()
+    }
+}
+
+impl AsRef<InputStream> for DataInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &InputStream {
+        use cast::AsInputStream;
+        self.as_input_stream()
+    }
+}
+
+impl AsRef<gobject::Object> for DataInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<Seekable> for DataInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &Seekable {
+        use cast::AsSeekable;
+        self.as_seekable()
+    }
+}
+
+impl AsRef<FilterOutputStream> for FilterOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &FilterOutputStream {
+        self
+    }
+}
+
+impl AsRef<OutputStream> for FilterOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &OutputStream {
+        use cast::AsOutputStream;
+        self.as_output_stream()
+    }
+}
+
+impl AsRef<gobject::Object> for FilterOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<BufferedOutputStream> for BufferedOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &BufferedOutputStream {
+        self
+    }
+}
+
+impl AsRef<FilterOutputStream> for BufferedOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &FilterOutputStream {
+        use cast::AsFilterOutputStream;
+        self.as_filter_output_stream()
+    }
+}
+
+impl AsRef<OutputStream> for BufferedOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &OutputStream {
+        use cast::AsOutputStream;
+        self.as_output_stream()
+    }
+}
+
+impl AsRef<gobject::Object> for BufferedOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<Seekable> for BufferedOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &Seekable {
+        use cast::AsSeekable;
+        self.as_seekable()
+    }
+}
+
+impl AsRef<DataOutputStream> for DataOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &DataOutputStream {
+        self
+    }
+}
+
+impl AsRef<FilterOutputStream> for DataOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &FilterOutputStream {
+        use cast::AsFilterOutputStream;
+        self.as_filter_output_stream()
+    }
+}
+
+impl AsRef<OutputStream> for DataOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &OutputStream {
+        use cast::AsOutputStream;
+        self.as_output_stream()
+    }
+}
+
+impl AsRef<gobject::Object> for DataOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<Seekable> for DataOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &Seekable {
+        use cast::AsSeekable;
+        self.as_seekable()
+    }
+}
+
+impl AsRef<Converter> for Converter {
+
+    #[inline]
+    fn as_ref(&self) -> &Converter {
+        self
+    }
+}
+
+impl AsRef<ZlibCompressor> for ZlibCompressor {
+
+    #[inline]
+    fn as_ref(&self) -> &ZlibCompressor {
+        self
+    }
+}
+
+impl AsRef<gobject::Object> for ZlibCompressor {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<Converter> for ZlibCompressor {
+
+    #[inline]
+    fn as_ref(&self) -> &Converter {
+        use cast::AsConverter;
+        self.as_converter()
+    }
+}
+
+impl AsRef<ZlibDecompressor> for ZlibDecompressor {
+
+    #[inline]
+    fn as_ref(&self) -> &ZlibDecompressor {
+        self
+    }
+}
+
+impl AsRef<gobject::Object> for ZlibDecompressor {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<Converter> for ZlibDecompressor {
+
+    #[inline]
+    fn as_ref(&self) -> &Converter {
+        use cast::AsConverter;
+        self.as_converter()
+    }
+}
+
+impl AsRef<CharsetConverter> for CharsetConverter {
+
+    #[inline]
+    fn as_ref(&self) -> &CharsetConverter {
+        self
+    }
+}
+
+impl AsRef<gobject::Object> for CharsetConverter {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
+
+impl AsRef<Converter> for CharsetConverter {
+
+    #[inline]
+    fn as_ref(&self) -> &Converter {
+        use cast::AsConverter;
+        self.as_converter()
+    }
+}
+
+impl AsRef<ConverterInputStream> for ConverterInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &ConverterInputStream {
+        self
+    }
+}
+
+impl AsRef<FilterInputStream> for ConverterInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &FilterInputStream {
+        use cast::AsFilterInputStream;
+        self.as_filter_input_stream()
+    }
+}
+
+impl AsRef<InputStream> for ConverterInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &InputStream {
+        use cast::AsInputStream;
+        self.as_input_stream()
     }
+}
 
-    fn name(&self) -> &'static str {
-        match *self {
-            IOErrorEnum::Failed => "failed",
-            IOErrorEnum::NotFound => "not-found",
-            IOErrorEnum::Exists   => "exists",
-            // ...
-        }
+impl AsRef<gobject::Object> for ConverterInputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
     }
 }
 
-impl enumeration::EnumType for IOErrorEnum {
-    fn get_type() -> GType {
-        unsafe { GType::from_raw(ffi::g_io_error_enum_get_type()) }
+impl AsRef<ConverterOutputStream> for ConverterOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &ConverterOutputStream {
+        self
     }
 }
 
-impl error::Domain for IOErrorEnum {
-    fn domain() -> quark::Quark {
-        g_static_quark!(b"g-io-error-quark\0")
+impl AsRef<FilterOutputStream> for ConverterOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &FilterOutputStream {
+        use cast::AsFilterOutputStream;
+        self.as_filter_output_stream()
     }
 }
 
-impl fmt::Display for IOErrorEnum {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.name())
+impl AsRef<OutputStream> for ConverterOutputStream {
+
+    #[inline]
+    fn as_ref(&self) -> &OutputStream {
+        use cast::AsOutputStream;
+        self.as_output_stream()
     }
 }
 
-pub mod flags {
+impl AsRef<gobject::Object> for ConverterOutputStream {
 
-    pub mod file_attribute_info {
-        use grust::flags::prelude::*;
-        use ffi;
+    #[inline]
+    fn as_ref(&self) -> &gobject::Object {
+        use gobject::cast::AsObject;
+        self.as_object()
+    }
+}
 
-        bitflags! {
-            pub flags Flags: ::grust::types::guint {
-                const NONE            = 0,
-                const COPY_WITH_FILE  = 1,
-                const COPY_WHEN_MOVED = 2,
-            }
-        }
+impl AsRef<Seekable> for Seekable {
 
-        impl IntrospectedFlags for Flags {
+    #[inline]
+    fn as_ref(&self) -> &Seekable {
+        self
+    }
+}
 
-            fn from_uint(v: guint) -> Result<Flags, UnknownFlags> {
-                Flags::from_bits(v)
-                    .ok_or_else(|| UnknownFlags::new(v, Flags::all().bits()))
-            }
+impl FileMonitor {
 
-            #[inline]
-            fn to_uint(&self) -> guint {
-                self.bits()
-            }
-        }
+    pub fn prop_cancelled(&self) -> bool {
+        use gobject::cast::AsObject;
+        self.as_object().get_property("cancelled").unwrap()
+    }
 
-        impl FlagsType for Flags {
-            fn get_type() -> GType {
-                unsafe {
-                    let raw = ffi::g_file_attribute_info_flags_get_type();
-                    GType::from_raw(raw)
-                }
-            }
-        }
+    pub fn prop_rate_limit(&self) -> i32 {
+        use gobject::cast::AsObject;
+        self.as_object().get_property("rate-limit").unwrap()
+    }
+
+    pub fn set_prop_rate_limit(&self, value: i32) {
+        use gobject::cast::AsObject;
+        self.as_object().set_property("rate-limit", value).unwrap()
     }
 }
 
-pub use flags::file_attribute_info::Flags as FileAttributeInfoFlags;
+impl IOStream {
 
-mod async {
-    use ffi;
-    use gobject_ffi;
-    use gobject;
+    pub fn prop_closed(&self) -> bool {
+        use gobject::cast::AsObject;
+        self.as_object().get_property("closed").unwrap()
+    }
+}
 
-    use grust::types::gpointer;
-    use grust::wrap;
-    use std::mem;
+impl FilterInputStream {
 
-    pub extern "C" fn async_ready_callback<F>(source_object: *mut gobject_ffi::GObject,
-                                              res: *mut ffi::GAsyncResult,
-                                              user_data: gpointer)
-        where F: FnOnce(&gobject::Object, &super::AsyncResult)
-    {
-        let cb: Box<F> = unsafe { mem::transmute(user_data) };
-        let arg1 = unsafe { wrap::from_raw::<gobject::Object>(source_object) };
-        let arg2 = unsafe { wrap::from_raw::<super::AsyncResult>(res) };
-        cb(arg1, arg2);
+    pub fn prop_close_base_stream(&self) -> bool {
+        use gobject::cast::AsObject;
+        self.as_object().get_property("close-base-stream").unwrap()
+    }
+
+    pub fn set_prop_close_base_stream(&self, value: bool) {
+        use gobject::cast::AsObject;
+        self.as_object().set_property("close-base-stream", value).unwrap()
     }
 }
 
-pub mod cast {
-    use grust::object;
+impl BufferedInputStream {
 
-    use gobject;
+    pub fn prop_buffer_size(&self) -> u32 {
+        use gobject::cast::AsObject;
+        self.as_object().get_property("buffer-size").unwrap()
+    }
 
-    pub trait AsAsyncResult {
-        fn as_async_result(&self) -> &super::AsyncResult;
+    pub fn set_prop_buffer_size(&self, value: u32) {
+        use gobject::cast::AsObject;
+        self.as_object().set_property("buffer-size", value).unwrap()
     }
+}
 
-    impl<T> AsAsyncResult for T where T: object::Upcast<super::AsyncResult> {
+impl FilterOutputStream {
 
-        #[inline]
-        fn as_async_result(&self) -> &super::AsyncResult {
-            self.upcast()
-        }
+    pub fn prop_close_base_stream(&self) -> bool {
+        use gobject::cast::AsObject;
+        self.as_object().get_property("close-base-stream").unwrap()
     }
+}
 
-    pub trait AsCancellable : gobject::cast::AsObject {
-        fn as_cancellable(&self) -> &super::Cancellable;
+impl BufferedOutputStream {
+
+    pub fn prop_auto_grow(&self) -> bool {
+        use gobject::cast::AsObject;
+        self.as_object().get_property("auto-grow").unwrap()
     }
 
-    impl<T> AsCancellable for T
-        where T: object::Upcast<super::Cancellable>,
-              T: object::Upcast<gobject::Object>
-    {
-        #[inline]
-        fn as_cancellable(&self) -> &super::Cancellable {
-            self.upcast()
-        }
+    pub fn set_prop_auto_grow(&self, value: bool) {
+        use gobject::cast::AsObject;
+        self.as_object().set_property("auto-grow", value).unwrap()
     }
 
-    pub trait AsInputStream : gobject::cast::AsObject {
-        fn as_input_stream(&self) -> &super::InputStream;
+    pub fn prop_buffer_size(&self) -> u32 {
+        use gobject::cast::AsObject;
+        self.as_object().get_property("buffer-size").unwrap()
     }
 
-    impl<T> AsInputStream for T
-        where T: object::Upcast<super::InputStream>,
-              T: object::Upcast<gobject::Object>
-    {
-        #[inline]
-        fn as_input_stream(&self) -> &super::InputStream {
-            self.upcast()
-        }
+    pub fn set_prop_buffer_size(&self, value: u32) {
+        use gobject::cast::AsObject;
+        self.as_object().set_property("buffer-size", value).unwrap()
     }
+}
 
-    pub trait AsFileInputStream : AsInputStream {
-        fn as_file_input_stream(&self) -> &super::FileInputStream;
+impl ZlibCompressor {
+
+    pub fn prop_level(&self) -> i32 {
+        use gobject::cast::AsObject;
+        self.as_object().get_property("level").unwrap()
     }
+}
 
-    impl<T> AsFileInputStream for T
-        where T: object::Upcast<super::FileInputStream>,
-              T: object::Upcast<super::InputStream>,
-              T: object::Upcast<gobject::Object>
-    {
-        #[inline]
-        fn as_file_input_stream(&self) -> &super::FileInputStream {
-            self.upcast()
-        }
+impl CharsetConverter {
+
+    pub fn prop_from_charset(&self) -> String {
+        use gobject::cast::AsObject;
+        self.as_object().get_property("from-charset").unwrap()
     }
 
-    pub trait AsFile {
-        fn as_file(&self) -> &super::File;
+    pub fn prop_to_charset(&self) -> String {
+        use gobject::cast::AsObject;
+        self.as_object().get_property("to-charset").unwrap()
     }
 
-    impl<T> AsFile for T where T: object::Upcast<super::File> {
+    pub fn prop_use_fallback(&self) -> bool {
+        use gobject::cast::AsObject;
+        self.as_object().get_property("use-fallback").unwrap()
+    }
 
-        #[inline]
-        fn as_file(&self) -> &super::File {
-            self.upcast()
-        }
+    pub fn set_prop_use_fallback(&self, value: bool) {
+        use gobject::cast::AsObject;
+        self.as_object().set_property("use-fallback", value).unwrap()
     }
 }
 
-impl File {
+impl Cancellable {
 
-    pub fn new_for_path(path: &gstr::Utf8) -> refcount::Ref<File> {
-        unsafe {
-            let ret = ffi::g_file_new_for_path(path.as_ptr());
-            refcount::Ref::from_raw(ret)
-        }
+    /// Connects a closure to the `cancelled` signal.
+    pub fn connect_cancelled<F>(&self, callback: F) -> gobject::SignalHandler
+        where F: Fn(&Cancellable) + Send + Sync + 'static
+    {
+        use gobject::cast::AsObject;
+        let object = self.as_object();
+        let id = object.connect("cancelled", move |args| {
+            callback(unsafe { gobject::value_as_object(&args[0]) });
+            None
+        }).unwrap();
+        gobject::SignalHandler::new(object, id)
     }
+}
 
-    pub fn get_path(&self) -> gstr::OwnedGStr {
-        unsafe {
-            use grust::wrap::Wrapper;
-            let ret = ffi::g_file_get_path(self.as_mut_ptr());
-            gstr::OwnedGStr::from_ptr(ret)
-        }
-    }
+impl IOErrorEnum {
 
-    pub fn read_async<F>(&self,
-                         io_priority: gint,
-                         cancellable: Option<&Cancellable>,
-                         callback: F)
-        where F: FnOnce(&gobject::Object, &AsyncResult),
-              F: Send + 'static
+    /// Returns the code GIO reports for a failure
+    /// with the POSIX error number `err_no`.
+    pub fn from_errno(err_no: i32)
+                      -> result::Result<IOErrorEnum, enumeration::UnknownValue>
     {
-        unsafe {
-            use grust::wrap::Wrapper;
-            let self_raw = self.as_mut_ptr();
-            let cancellable = {
-                match cancellable {
-                    Some(c) => c.as_mut_ptr(),
-                    None    => std::ptr::null_mut()
-                }
-            };
-            let callback: gpointer = mem::transmute(Box::new(callback));
+        let code = unsafe { ffi::g_io_error_from_errno(err_no as gint) };
+        IOErrorEnum::from_int(code as gint)
+    }
+}
 
-            ffi::g_file_read_async(self_raw,
-                                   io_priority,
-                                   cancellable,
-                                   Some(async::async_ready_callback::<F>),
-                                   callback);
-        }
+fn io_error_kind(code: IOErrorEnum) -> io::ErrorKind {
+    match code {
+        IOErrorEnum::NotFound           => io::ErrorKind::NotFound,
+        IOErrorEnum::PermissionDenied   => io::ErrorKind::PermissionDenied,
+        IOErrorEnum::ConnectionRefused  => io::ErrorKind::ConnectionRefused,
+        IOErrorEnum::NotConnected       => io::ErrorKind::NotConnected,
+        IOErrorEnum::AddressInUse       => io::ErrorKind::AddrInUse,
+        IOErrorEnum::BrokenPipe         => io::ErrorKind::BrokenPipe,
+        IOErrorEnum::Exists             => io::ErrorKind::AlreadyExists,
+        IOErrorEnum::WouldBlock         => io::ErrorKind::WouldBlock,
+        IOErrorEnum::InvalidArgument    => io::ErrorKind::InvalidInput,
+        IOErrorEnum::InvalidData        => io::ErrorKind::InvalidData,
+        IOErrorEnum::PartialInput       => io::ErrorKind::UnexpectedEof,
+        IOErrorEnum::TimedOut           => io::ErrorKind::TimedOut,
+        _                               => io::ErrorKind::Other
     }
+}
 
-    pub fn read_finish(&self, res: &AsyncResult)
-                      -> result::Result<refcount::Ref<FileInputStream>,
-                                        error::Error> {
-        let mut err: *mut glib_ffi::GError = ptr::null_mut();
-        let ret = unsafe {
-            use grust::wrap::Wrapper;
-            ffi::g_file_read_finish(self.as_mut_ptr(),
-                                    res.as_mut_ptr(),
-                                    &mut err)
-        };
-        if err.is_null() {
-            Ok(unsafe { refcount::Ref::from_raw(ret) })
-        } else {
-            Err(unsafe { error::Error::from_raw(err) })
+fn to_io_error(err: error::Error) -> io::Error {
+    let message = format!("{}", err);
+    let reid: result::Result<error::DomainError<IOErrorEnum>, error::Error>
+              = err.into_domain();
+    let kind = match reid {
+        Ok(io_error) => {
+            match io_error.code() {
+                error::Code::Known(code) => io_error_kind(code),
+                error::Code::Unknown(_)  => io::ErrorKind::Other
+            }
         }
+        Err(_) => io::ErrorKind::Other
+    };
+    io::Error::new(kind, message)
+}
+
+impl<'a> io::Read for &'a InputStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        InputStream::read(*self, buf, None).map_err(to_io_error)
     }
 }
 
-unsafe impl object::ObjectType for AsyncResult {
-    fn get_type() -> GType {
-        unsafe {
-            GType::from_raw(ffi::g_async_result_get_type())
-        }
+impl<'a> io::Read for &'a FileInputStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        use cast::AsInputStream;
+        self.as_input_stream().read(buf, None).map_err(to_io_error)
     }
 }
 
-unsafe impl object::ObjectType for File {
-    fn get_type() -> GType {
-        unsafe {
-            GType::from_raw(ffi::g_file_get_type())
-        }
+impl<'a> io::Read for &'a MemoryInputStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        use cast::AsInputStream;
+        self.as_input_stream().read(buf, None).map_err(to_io_error)
     }
 }
 
-unsafe impl object::ObjectType for InputStream {
-    fn get_type() -> GType {
-        unsafe {
-            GType::from_raw(ffi::g_input_stream_get_type())
-        }
+impl<'a> io::Read for &'a ConverterInputStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        use cast::AsInputStream;
+        self.as_input_stream().read(buf, None).map_err(to_io_error)
     }
 }
 
-unsafe impl object::ObjectType for FileInputStream {
-    fn get_type() -> GType {
-        unsafe {
-            GType::from_raw(ffi::g_file_input_stream_get_type())
-        }
+impl<'a> io::Read for &'a BufferedInputStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        use cast::AsInputStream;
+        self.as_input_stream().read(buf, None).map_err(to_io_error)
     }
 }
 
-impl object::Upcast<gobject::Object> for Cancellable {
+impl<'a> io::Write for &'a OutputStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        OutputStream::write(*self, buf, None).map_err(to_io_error)
+    }
 
-    #[inline]
-    fn upcast(&self) -> &gobject::Object {
-        unsafe {
-            wrap::from_raw(&self.raw.parent_instance)
-        }
+    fn flush(&mut self) -> io::Result<()> {
+        OutputStream::flush(*self, None).map_err(to_io_error)
     }
 }
 
-impl object::Upcast<gobject::Object> for InputStream {
+impl<'a> io::Write for &'a FileOutputStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        use cast::AsOutputStream;
+        self.as_output_stream().write(buf, None).map_err(to_io_error)
+    }
 
-    #[inline]
-    fn upcast(&self) -> &gobject::Object {
-        unsafe {
-            wrap::from_raw(&self.raw.parent_instance)
-        }
+    fn flush(&mut self) -> io::Result<()> {
+        use cast::AsOutputStream;
+        self.as_output_stream().flush(None).map_err(to_io_error)
     }
 }
 
-impl object::Upcast<InputStream> for FileInputStream {
+impl<'a> io::Write for &'a MemoryOutputStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        use cast::AsOutputStream;
+        self.as_output_stream().write(buf, None).map_err(to_io_error)
+    }
 
-    #[inline]
-    fn upcast(&self) -> &InputStream {
-        unsafe {
-            wrap::from_raw(&self.raw.parent_instance)
-        }
+    fn flush(&mut self) -> io::Result<()> {
+        use cast::AsOutputStream;
+        self.as_output_stream().flush(None).map_err(to_io_error)
     }
 }
 
-impl object::Upcast<gobject::Object> for FileInputStream {
+impl<'a> io::Write for &'a BufferedOutputStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        use cast::AsOutputStream;
+        self.as_output_stream().write(buf, None).map_err(to_io_error)
+    }
 
-    #[inline]
-    fn upcast(&self) -> &gobject::Object {
-        use cast::AsInputStream;
-        self.as_input_stream().upcast()
+    fn flush(&mut self) -> io::Result<()> {
+        use cast::AsOutputStream;
+        self.as_output_stream().flush(None).map_err(to_io_error)
+    }
+}
+
+impl<'a> io::Write for &'a ConverterOutputStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        use cast::AsOutputStream;
+        self.as_output_stream().write(buf, None).map_err(to_io_error)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        use cast::AsOutputStream;
+        self.as_output_stream().flush(None).map_err(to_io_error)
+    }
+}
+
+impl<'a> io::Seek for &'a Seekable {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        let (offset, seek_type) = match pos {
+            io::SeekFrom::Start(n) if n > i64::MAX as u64 => {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                          "seek offset out of range"));
+            }
+            io::SeekFrom::Start(n)   => (n as i64, glib::SeekType::Set),
+            io::SeekFrom::Current(n) => (n, glib::SeekType::Cur),
+            io::SeekFrom::End(n)     => (n, glib::SeekType::End),
+        };
+        match Seekable::seek(*self, offset, seek_type, None) {
+            Ok(()) => Ok(self.tell() as u64),
+            Err(e) => Err(to_io_error(e))
+        }
+    }
+}
+
+impl<'a> io::Seek for &'a FileInputStream {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        use cast::AsSeekable;
+        io::Seek::seek(&mut self.as_seekable(), pos)
     }
 }
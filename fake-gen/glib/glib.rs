@@ -21,3 +21,638 @@
 
 extern crate grust;
 extern crate glib_2_0_sys as ffi;
+
+use grust::enumeration;
+use grust::enumeration::IntrospectedEnum as _grust_IntrospectedEnumTrait;
+use grust::types::{gconstpointer, gint, gpointer, gsize};
+
+use std::fmt;
+use std::mem;
+use std::ops::Deref;
+use std::slice;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub enum SeekType {
+    Cur = 0,
+    Set = 1,
+    End = 2,
+}
+
+impl enumeration::IntrospectedEnum for SeekType {
+
+    fn from_int(v: gint) -> Result<Self, enumeration::UnknownValue> {
+        match v {
+            0 => Ok(SeekType::Cur),
+            1 => Ok(SeekType::Set),
+            2 => Ok(SeekType::End),
+            _ => Err(enumeration::UnknownValue(v))
+        }
+    }
+
+    fn to_int(&self) -> gint {
+        *self as gint
+    }
+
+    fn name(&self) -> &'static str {
+        match *self {
+            SeekType::Cur => "cur",
+            SeekType::Set => "set",
+            SeekType::End => "end",
+        }
+    }
+}
+
+impl fmt::Display for SeekType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// An immutable, reference-counted byte buffer backed by `GBytes`.
+///
+/// Cloning a `Bytes` value adds a reference to the same buffer.
+/// The contents can be borrowed as `&[u8]` without copying.
+pub struct Bytes {
+    raw: *mut ffi::GBytes,
+}
+
+// GBytes is immutable and its reference count is atomic
+unsafe impl Send for Bytes { }
+unsafe impl Sync for Bytes { }
+
+extern "C" fn free_vec(data: gpointer) {
+    let _: Box<Vec<u8>> = unsafe { Box::from_raw(data as *mut Vec<u8>) };
+}
+
+impl Bytes {
+
+    /// Creates a buffer referring to static data, without copying.
+    pub fn from_static(data: &'static [u8]) -> Bytes {
+        unsafe {
+            let raw = ffi::g_bytes_new_static(data.as_ptr() as gconstpointer,
+                                              data.len() as gsize);
+            Bytes::from_raw(raw)
+        }
+    }
+
+    /// Takes ownership of a `GBytes` reference.
+    pub unsafe fn from_raw(raw: *mut ffi::GBytes) -> Bytes {
+        assert!(!raw.is_null());
+        Bytes { raw: raw }
+    }
+
+    /// Returns the pointer to the underlying `GBytes`.
+    ///
+    /// The reference is owned by this value, so callers that keep
+    /// the pointer must add a reference of their own.
+    pub fn as_mut_ptr(&self) -> *mut ffi::GBytes {
+        self.raw
+    }
+}
+
+/// Wraps the vector without copying; the vector is dropped
+/// when the last reference to the buffer is released.
+impl From<Vec<u8>> for Bytes {
+    fn from(vec: Vec<u8>) -> Bytes {
+        let data = vec.as_ptr() as gconstpointer;
+        let size = vec.len() as gsize;
+        let vec: gpointer = unsafe { mem::transmute(Box::new(vec)) };
+        unsafe {
+            let raw = ffi::g_bytes_new_with_free_func(data, size,
+                                                      Some(free_vec), vec);
+            Bytes::from_raw(raw)
+        }
+    }
+}
+
+impl Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        let mut size: gsize = 0;
+        unsafe {
+            let data = ffi::g_bytes_get_data(self.raw, &mut size);
+            if size == 0 {
+                &[]
+            } else {
+                slice::from_raw_parts(data as *const u8, size as usize)
+            }
+        }
+    }
+}
+
+impl AsRef<[u8]> for Bytes {
+    fn as_ref(&self) -> &[u8] {
+        &**self
+    }
+}
+
+impl Clone for Bytes {
+    fn clone(&self) -> Bytes {
+        unsafe {
+            Bytes::from_raw(ffi::g_bytes_ref(self.raw))
+        }
+    }
+}
+
+impl Drop for Bytes {
+    fn drop(&mut self) {
+        unsafe { ffi::g_bytes_unref(self.raw) };
+    }
+}
+
+impl PartialEq for Bytes {
+    fn eq(&self, other: &Bytes) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for Bytes { }
+
+impl fmt::Debug for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Bytes").field(&&**self).finish()
+    }
+}
+
+pub mod unwind {
+    //! Containment of panics in Rust code called back from C.
+    //!
+    //! Unwinding across an FFI boundary is undefined behavior, so
+    //! callback trampolines run the Rust closures with `catch_panic`.
+    //! A caught panic is stored in the thread where it occurred, and
+    //! resumed when control returns to Rust code driving the main loop:
+    //! `run_loop`, or the `executor` methods. Code that calls into C
+    //! synchronously can enter a `ResumeScope` and resume the panic
+    //! itself once the call returns.
+    //!
+    //! Callbacks that may be dispatched outside of all of these, such as
+    //! by a main loop run directly with `LoopRunner`, use
+    //! `catch_panic_or_abort`: the process is aborted rather than left
+    //! waiting on a loop that nothing will quit, with the panic lost.
+
+    use grust::mainloop::{LoopRunner, MainLoop};
+    use grust::refcount::Ref;
+
+    use std::any::Any;
+    use std::cell::{Cell, RefCell};
+    use std::panic;
+    use std::panic::AssertUnwindSafe;
+    use std::process;
+
+    thread_local! {
+        static PANIC: RefCell<Option<Box<dyn Any + Send>>> = RefCell::new(None);
+        static LOOPS: RefCell<Vec<Ref<MainLoop>>> = RefCell::new(Vec::new());
+        static SCOPES: Cell<usize> = Cell::new(0);
+    }
+
+    /// Calls a closure on behalf of C code, catching any panic.
+    ///
+    /// If the closure panics, the payload is stored to be resumed
+    /// later by `resume_if_panicked`, the innermost main loop run by
+    /// `run_loop` in this thread is made to quit, and `None` is returned.
+    pub fn catch_panic<F, R>(f: F) -> Option<R> where F: FnOnce() -> R {
+        match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(ret) => Some(ret),
+            Err(payload) => {
+                PANIC.with(|p| {
+                    let mut p = p.borrow_mut();
+                    // Keep the first panic, later ones are likely
+                    // to be its consequences
+                    if p.is_none() {
+                        *p = Some(payload);
+                    }
+                });
+                LOOPS.with(|loops| {
+                    if let Some(mainloop) = loops.borrow().last() {
+                        mainloop.quit();
+                    }
+                });
+                None
+            }
+        }
+    }
+
+    /// Calls a closure on behalf of C code like `catch_panic`,
+    /// but aborts the process if the panic would not be resumed
+    /// in this thread: no main loop is run by `run_loop` and no
+    /// `ResumeScope` is active.
+    ///
+    /// This is meant for callbacks that may be invoked from any code
+    /// calling into C, such as signal handlers, where the panic would
+    /// otherwise be resumed in some unrelated later call, or never.
+    pub fn catch_panic_or_abort<F, R>(f: F) -> Option<R>
+        where F: FnOnce() -> R
+    {
+        let ret = catch_panic(f);
+        if ret.is_none() && !will_resume() {
+            eprintln!("panic in a callback invoked outside of a main loop, \
+                       executor, or other scope resuming it; aborting");
+            process::abort();
+        }
+        ret
+    }
+
+    /// Returns whether a panic caught in this thread would be resumed.
+    pub fn will_resume() -> bool {
+        SCOPES.with(|n| n.get() != 0)
+            || LOOPS.with(|loops| !loops.borrow().is_empty())
+    }
+
+    /// Resumes a panic previously caught by `catch_panic`
+    /// in this thread, if any.
+    pub fn resume_if_panicked() {
+        if let Some(payload) = PANIC.with(|p| p.borrow_mut().take()) {
+            panic::resume_unwind(payload);
+        }
+    }
+
+    /// Marks code that resumes caught panics with `resume_if_panicked`
+    /// once its calls into C return, for as long as the scope is alive.
+    pub struct ResumeScope {
+        _private: (),
+    }
+
+    impl ResumeScope {
+
+        pub fn enter() -> ResumeScope {
+            SCOPES.with(|n| n.set(n.get() + 1));
+            ResumeScope { _private: () }
+        }
+    }
+
+    impl Drop for ResumeScope {
+        fn drop(&mut self) {
+            SCOPES.with(|n| n.set(n.get() - 1));
+        }
+    }
+
+    struct LoopGuard;
+
+    impl Drop for LoopGuard {
+        fn drop(&mut self) {
+            LOOPS.with(|loops| { loops.borrow_mut().pop(); });
+        }
+    }
+
+    /// Runs a main loop with `LoopRunner`, calling `setup` before
+    /// the loop is started.
+    ///
+    /// If a callback invoked from the main loop panics, the loop is quit
+    /// and the panic is resumed in the calling thread.
+    pub fn run_loop<F>(setup: F) where F: FnOnce(Ref<MainLoop>) {
+        let runner = LoopRunner::new();
+        {
+            // The guard only exists once the loop has been pushed,
+            // so a failure before that won't pop an outer loop
+            let mut guard = None;
+            runner.run_after(|mainloop| {
+                LOOPS.with(|loops| loops.borrow_mut().push(mainloop.clone()));
+                guard = Some(LoopGuard);
+                setup(mainloop);
+            });
+            drop(guard);
+        }
+        resume_if_panicked();
+    }
+}
+
+pub mod timeout {
+    //! One-shot timeout sources.
+
+    use ffi;
+    use crate::unwind;
+
+    use grust::types::{gboolean, gpointer, guint, FALSE};
+
+    /// A timeout source attached to a main context.
+    ///
+    /// The source is destroyed when this value is dropped, so the
+    /// callback will not be called after that.
+    pub struct Timeout {
+        source: *mut ffi::GSource,
+    }
+
+    /// Calls `callback` once, after `interval` milliseconds have passed,
+    /// from the thread-default main context of the calling thread.
+    ///
+    /// A panic in the callback is handled as described in `unwind`.
+    pub fn add_once<F>(interval: guint, callback: F) -> Timeout
+        where F: FnOnce() + 'static
+    {
+        let data: Box<Option<F>> = Box::new(Some(callback));
+        unsafe {
+            let source = ffi::g_timeout_source_new(interval);
+            ffi::g_source_set_callback(source,
+                                       Some(timeout_once::<F>),
+                                       Box::into_raw(data) as gpointer,
+                                       Some(free_callback::<F>));
+            let context = ffi::g_main_context_get_thread_default();
+            ffi::g_source_attach(source, context);
+            Timeout { source: source }
+        }
+    }
+
+    extern "C" fn timeout_once<F>(data: gpointer) -> gboolean
+        where F: FnOnce()
+    {
+        let callback = unsafe { &mut *(data as *mut Option<F>) };
+        if let Some(f) = callback.take() {
+            unwind::catch_panic_or_abort(f);
+        }
+        FALSE
+    }
+
+    extern "C" fn free_callback<F>(data: gpointer) {
+        let _: Box<Option<F>> = unsafe {
+            Box::from_raw(data as *mut Option<F>)
+        };
+    }
+
+    impl Drop for Timeout {
+        fn drop(&mut self) {
+            unsafe {
+                ffi::g_source_destroy(self.source);
+                ffi::g_source_unref(self.source);
+            }
+        }
+    }
+}
+
+pub mod executor {
+    //! Running Rust futures on GLib main contexts.
+
+    use ffi;
+    use crate::unwind;
+
+    use grust::mainloop::MainContext;
+    use grust::refcount::Ref;
+    use grust::types::{gboolean, gpointer, guint, FALSE, TRUE};
+    use grust::wrap::Wrapper;
+
+    use std::future::Future;
+    use std::mem;
+    use std::pin::Pin;
+    use std::ptr;
+    use std::sync::Arc;
+    use std::process;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread;
+    use std::thread::ThreadId;
+
+    /// Creates a new main context, not owned by any thread.
+    pub fn new_context() -> Ref<MainContext> {
+        unsafe { Ref::from_raw(ffi::g_main_context_new()) }
+    }
+
+    /// Methods to drive futures with a main context.
+    pub trait Executor {
+
+        /// Runs a future to completion, iterating the main context
+        /// in the calling thread while the future is pending.
+        ///
+        /// The context is acquired and made the thread-default context
+        /// for the duration of the call, so GIO asynchronous operations
+        /// started while polling the future are dispatched by it.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the context is owned by another thread.
+        fn block_on<F>(&self, future: F) -> F::Output where F: Future;
+
+        /// Spawns a future to be polled by the main context until
+        /// it completes.
+        ///
+        /// The future is only polled in the calling thread, by code
+        /// iterating the context there, such as `block_on` or a main loop.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the calling thread does not own the context,
+        /// as it does while running `block_on` or a main loop on it.
+        /// If the context is later iterated in another thread while
+        /// the future is pending, the process is aborted.
+        fn spawn_local<F>(&self, future: F)
+            where F: Future<Output = ()> + 'static;
+    }
+
+    struct ThreadDefault(*mut ffi::GMainContext);
+
+    impl Drop for ThreadDefault {
+        fn drop(&mut self) {
+            unsafe {
+                ffi::g_main_context_pop_thread_default(self.0);
+                ffi::g_main_context_release(self.0);
+            }
+        }
+    }
+
+    struct ContextWaker {
+        context: *mut ffi::GMainContext,
+        woken: AtomicBool,
+    }
+
+    // GMainContext can be woken up from any thread
+    unsafe impl Send for ContextWaker { }
+    unsafe impl Sync for ContextWaker { }
+
+    impl Wake for ContextWaker {
+        fn wake(self: Arc<Self>) {
+            self.wake_by_ref();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.woken.store(true, Ordering::SeqCst);
+            unsafe { ffi::g_main_context_wakeup(self.context) };
+        }
+    }
+
+    impl Drop for ContextWaker {
+        fn drop(&mut self) {
+            unsafe { ffi::g_main_context_unref(self.context) };
+        }
+    }
+
+    #[repr(C)]
+    struct TaskSource {
+        source: ffi::GSource,
+        future: Option<Pin<Box<dyn Future<Output = ()>>>>,
+        thread: ThreadId,
+    }
+
+    struct SourceWaker(*mut ffi::GSource);
+
+    // g_source_set_ready_time() is thread-safe
+    unsafe impl Send for SourceWaker { }
+    unsafe impl Sync for SourceWaker { }
+
+    impl Wake for SourceWaker {
+        fn wake(self: Arc<Self>) {
+            self.wake_by_ref();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            unsafe { ffi::g_source_set_ready_time(self.0, 0) };
+        }
+    }
+
+    impl Drop for SourceWaker {
+        fn drop(&mut self) {
+            unsafe { ffi::g_source_unref(self.0) };
+        }
+    }
+
+    unsafe extern "C" fn task_dispatch(source: *mut ffi::GSource,
+                                       _callback: ffi::GSourceFunc,
+                                       _user_data: gpointer)
+                                       -> gboolean
+    {
+        ffi::g_source_set_ready_time(source, -1);
+        let task = &mut *(source as *mut TaskSource);
+        if task.thread != thread::current().id() {
+            // The future is not Send, and a panic cannot unwind
+            // through the C code iterating the context
+            eprintln!("a task spawned with spawn_local was dispatched \
+                       in another thread");
+            process::abort();
+        }
+        let done = match task.future {
+            Some(ref mut future) => {
+                let waker = Arc::new(SourceWaker(ffi::g_source_ref(source)));
+                let waker = Waker::from(waker);
+                let mut cx = Context::from_waker(&waker);
+                // A panicking task is dropped, the panic is resumed
+                // by the code iterating the context
+                unwind::catch_panic(|| future.as_mut().poll(&mut cx).is_ready())
+                    .unwrap_or(true)
+            }
+            None => true
+        };
+        if done {
+            // Dropping the future releases any wakers it holds,
+            // which would otherwise keep the source alive
+            task.future = None;
+            FALSE
+        } else {
+            TRUE
+        }
+    }
+
+    unsafe extern "C" fn task_finalize(source: *mut ffi::GSource) {
+        let task = &mut *(source as *mut TaskSource);
+        if task.thread == thread::current().id() {
+            ptr::drop_in_place(&mut task.future);
+        } else {
+            // Leak the future rather than drop it in a thread
+            // it may not be sent to
+            mem::forget(task.future.take());
+        }
+        ptr::drop_in_place(&mut task.thread);
+    }
+
+    static TASK_SOURCE_FUNCS: ffi::GSourceFuncs = ffi::GSourceFuncs {
+        prepare: None,
+        check: None,
+        dispatch: Some(task_dispatch),
+        finalize: Some(task_finalize),
+        closure_callback: None,
+        closure_marshal: None,
+    };
+
+    impl Executor for MainContext {
+
+        fn block_on<F>(&self, future: F) -> F::Output where F: Future {
+            let context = self.as_mut_ptr();
+            let _scope = unwind::ResumeScope::enter();
+            let _thread_default = unsafe {
+                if ffi::g_main_context_acquire(context) == FALSE {
+                    panic!("main context is owned by another thread");
+                }
+                ffi::g_main_context_push_thread_default(context);
+                ThreadDefault(context)
+            };
+            let waker = Arc::new(ContextWaker {
+                context: unsafe { ffi::g_main_context_ref(context) },
+                woken: AtomicBool::new(true),
+            });
+            let mut future = Box::pin(future);
+            loop {
+                if waker.woken.swap(false, Ordering::SeqCst) {
+                    let w = Waker::from(waker.clone());
+                    let mut cx = Context::from_waker(&w);
+                    if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                        return output;
+                    }
+                }
+                let may_block = if waker.woken.load(Ordering::SeqCst) {
+                    FALSE
+                } else {
+                    TRUE
+                };
+                unsafe { ffi::g_main_context_iteration(context, may_block) };
+                unwind::resume_if_panicked();
+            }
+        }
+
+        fn spawn_local<F>(&self, future: F)
+            where F: Future<Output = ()> + 'static
+        {
+            let context = self.as_mut_ptr();
+            if unsafe { ffi::g_main_context_is_owner(context) } == FALSE {
+                panic!("main context is not owned by the calling thread");
+            }
+            unsafe {
+                // GLib does not modify the functions
+                let funcs = &TASK_SOURCE_FUNCS as *const ffi::GSourceFuncs
+                            as *mut ffi::GSourceFuncs;
+                let source = ffi::g_source_new(funcs,
+                                               mem::size_of::<TaskSource>()
+                                               as guint);
+                let task = source as *mut TaskSource;
+                let future: Pin<Box<dyn Future<Output = ()>>> = Box::pin(future);
+                ptr::write(&mut (*task).future, Some(future));
+                ptr::write(&mut (*task).thread, thread::current().id());
+                ffi::g_source_set_ready_time(source, 0);
+                ffi::g_source_attach(source, context);
+                ffi::g_source_unref(source);
+            }
+        }
+    }
+
+    /// A future that creates another future on the first poll.
+    ///
+    /// This can be used to defer starting an asynchronous operation
+    /// until the future is run by `Executor::block_on`, so that the
+    /// operation is dispatched by the executing context.
+    pub struct Lazy<F, R> {
+        init: Option<F>,
+        future: Option<R>,
+    }
+
+    pub fn lazy<F, R>(init: F) -> Lazy<F, R>
+        where F: FnOnce() -> R,
+              R: Future + Unpin
+    {
+        Lazy { init: Some(init), future: None }
+    }
+
+    impl<F, R> Future for Lazy<F, R>
+        where F: FnOnce() -> R + Unpin,
+              R: Future + Unpin
+    {
+        type Output = R::Output;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<R::Output> {
+            let this = &mut *self;
+            if let Some(init) = this.init.take() {
+                this.future = Some(init());
+            }
+            match this.future {
+                Some(ref mut future) => Pin::new(future).poll(cx),
+                None => unreachable!()
+            }
+        }
+    }
+}
@@ -20,12 +20,29 @@
 #![crate_type = "lib"]
 
 extern crate grust;
-extern crate grust_glib_2_0 as glib;
+
+#[macro_use]
+extern crate bitflags;
+
 extern crate gobject_2_0_sys as ffi;
+extern crate glib_2_0_sys as glib_ffi;
+extern crate grust_glib_2_0 as glib;
 
 use grust::gtype::GType;
 use grust::object;
+use grust::refcount::Ref;
+use grust::types::{gchar, gpointer, guint, gulong, FALSE, TRUE};
+use grust::value::Value;
 use grust::wrap;
+use grust::wrap::Wrapper;
+
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::mem;
+use std::ptr;
+use std::slice;
+use std::str;
 
 #[repr(C)]
 pub struct TypeInstance {
@@ -45,6 +62,55 @@ unsafe impl wrap::Wrapper for Object {
     type Raw = ffi::GObject;
 }
 
+pub mod flags {
+
+    pub mod param {
+        use grust::flags::prelude::*;
+        use ffi;
+
+        bitflags! {
+            pub flags Flags: ::grust::types::guint {
+                const READABLE        = 1,
+                const WRITABLE        = 2,
+                const READWRITE       = 3,
+                const CONSTRUCT       = 4,
+                const CONSTRUCT_ONLY  = 8,
+                const LAX_VALIDATION  = 16,
+                const STATIC_NAME     = 32,
+                const PRIVATE         = 32,
+                const STATIC_NICK     = 64,
+                const STATIC_BLURB    = 128,
+                const EXPLICIT_NOTIFY = 1073741824,
+                const DEPRECATED      = 2147483648,
+            }
+        }
+
+        impl IntrospectedFlags for Flags {
+
+            fn from_uint(v: guint) -> Result<Flags, UnknownFlags> {
+                Flags::from_bits(v)
+                    .ok_or_else(|| UnknownFlags::new(v, Flags::all().bits()))
+            }
+
+            #[inline]
+            fn to_uint(&self) -> guint {
+                self.bits()
+            }
+        }
+
+        impl FlagsType for Flags {
+            fn get_type() -> GType {
+                unsafe {
+                    let raw = ffi::g_param_flags_get_type();
+                    GType::from_raw(raw)
+                }
+            }
+        }
+    }
+}
+
+pub use flags::param::Flags as ParamFlags;
+
 pub mod cast {
     use grust::object;
 
@@ -66,3 +132,1042 @@ unsafe impl object::ObjectType for Object {
         }
     }
 }
+
+impl AsRef<Object> for Object {
+
+    #[inline]
+    fn as_ref(&self) -> &Object {
+        self
+    }
+}
+
+/// Describes a property of an object class.
+///
+/// Cloning a `ParamSpec` adds a reference to the same `GParamSpec`.
+pub struct ParamSpec {
+    raw: *mut ffi::GParamSpec,
+}
+
+impl ParamSpec {
+
+    /// Takes a new reference to a `GParamSpec`.
+    unsafe fn from_raw_ref(raw: *mut ffi::GParamSpec) -> ParamSpec {
+        ParamSpec { raw: ffi::g_param_spec_ref(raw) }
+    }
+
+    /// The canonical name of the property.
+    pub fn get_name(&self) -> &str {
+        unsafe {
+            let name = ffi::g_param_spec_get_name(self.raw);
+            // Property names are restricted to ASCII
+            str::from_utf8_unchecked(CStr::from_ptr(name).to_bytes())
+        }
+    }
+
+    pub fn get_nick(&self) -> Option<&str> {
+        unsafe { borrow_str(ffi::g_param_spec_get_nick(self.raw)) }
+    }
+
+    pub fn get_blurb(&self) -> Option<&str> {
+        unsafe { borrow_str(ffi::g_param_spec_get_blurb(self.raw)) }
+    }
+
+    /// The type of the property values.
+    pub fn value_type(&self) -> GType {
+        unsafe { GType::from_raw((*self.raw).value_type) }
+    }
+
+    /// The class or interface that introduced the property.
+    pub fn owner_type(&self) -> GType {
+        unsafe { GType::from_raw((*self.raw).owner_type) }
+    }
+
+    pub fn get_flags(&self) -> ParamFlags {
+        let flags = unsafe { (*self.raw).flags };
+        ParamFlags::from_bits_truncate(flags as guint)
+    }
+
+    pub fn as_mut_ptr(&self) -> *mut ffi::GParamSpec {
+        self.raw
+    }
+}
+
+impl Clone for ParamSpec {
+    fn clone(&self) -> ParamSpec {
+        unsafe { ParamSpec::from_raw_ref(self.raw) }
+    }
+}
+
+impl Drop for ParamSpec {
+    fn drop(&mut self) {
+        unsafe { ffi::g_param_spec_unref(self.raw) }
+    }
+}
+
+impl fmt::Debug for ParamSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ParamSpec")
+         .field("name", &self.get_name())
+         .field("value_type", &type_name(unsafe { (*self.raw).value_type }))
+         .field("flags", &self.get_flags())
+         .finish()
+    }
+}
+
+impl fmt::Debug for Object {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}({:p})", self.type_name(), self.as_ptr())
+    }
+}
+
+unsafe fn borrow_str<'a>(ptr: *const gchar) -> Option<&'a str> {
+    if ptr.is_null() {
+        None
+    } else {
+        str::from_utf8(CStr::from_ptr(ptr).to_bytes()).ok()
+    }
+}
+
+fn type_name(gtype: ffi::GType) -> String {
+    unsafe {
+        let name = ffi::g_type_name(gtype);
+        if name.is_null() {
+            format!("<invalid type {}>", gtype)
+        } else {
+            CStr::from_ptr(name).to_string_lossy().into_owned()
+        }
+    }
+}
+
+/// Errors in dynamic access to object properties.
+///
+/// Each variant carries the type name of the object
+/// and the name of the property.
+#[derive(Clone, Debug)]
+pub enum PropertyError {
+    /// The class has no property with the given name.
+    NotFound { class: String, name: String },
+    NotReadable { class: String, name: String },
+    /// The property is read-only, or can only be set at construction.
+    NotWritable { class: String, name: String },
+    /// The property values cannot be converted to or from
+    /// the requested type.
+    TypeMismatch {
+        class: String,
+        name: String,
+        property_type: String,
+        requested_type: String,
+    },
+}
+
+impl fmt::Display for PropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PropertyError::NotFound { ref class, ref name } => {
+                write!(f, "class {} has no property \"{}\"", class, name)
+            }
+            PropertyError::NotReadable { ref class, ref name } => {
+                write!(f, "property {}:{} is not readable", class, name)
+            }
+            PropertyError::NotWritable { ref class, ref name } => {
+                write!(f, "property {}:{} is not writable", class, name)
+            }
+            PropertyError::TypeMismatch { ref class, ref name,
+                                          ref property_type,
+                                          ref requested_type } => {
+                write!(f, "property {}:{} of type {} cannot hold {}",
+                       class, name, property_type, requested_type)
+            }
+        }
+    }
+}
+
+impl Error for PropertyError {
+    fn description(&self) -> &str {
+        match *self {
+            PropertyError::NotFound { .. }     => "property not found",
+            PropertyError::NotReadable { .. }  => "property is not readable",
+            PropertyError::NotWritable { .. }  => "property is not writable",
+            PropertyError::TypeMismatch { .. } => "property type mismatch",
+        }
+    }
+}
+
+// IDs of the fundamental types are fixed by the GType ABI
+const FUNDAMENTAL_SHIFT: usize = 2;
+const TYPE_BOOLEAN: ffi::GType = 5 << FUNDAMENTAL_SHIFT;
+const TYPE_INT:     ffi::GType = 6 << FUNDAMENTAL_SHIFT;
+const TYPE_UINT:    ffi::GType = 7 << FUNDAMENTAL_SHIFT;
+const TYPE_INT64:   ffi::GType = 10 << FUNDAMENTAL_SHIFT;
+const TYPE_UINT64:  ffi::GType = 11 << FUNDAMENTAL_SHIFT;
+const TYPE_DOUBLE:  ffi::GType = 15 << FUNDAMENTAL_SHIFT;
+const TYPE_STRING:  ffi::GType = 16 << FUNDAMENTAL_SHIFT;
+
+/// Rust types that property values can be converted to and from.
+///
+/// Values of other types, such as enumerations, can be accessed
+/// with `Object::get_property_value` and `Object::set_property_value`.
+pub trait PropertyType: Sized {
+
+    /// The type of `GValue` holding values of this type.
+    fn value_type() -> GType;
+
+    /// Reads the contents of a value initialized
+    /// with the type given by `value_type`.
+    unsafe fn from_value(value: &Value) -> Self;
+
+    /// Stores this into a value initialized
+    /// with the type given by `value_type`.
+    unsafe fn to_value(&self, value: &mut Value);
+}
+
+macro_rules! impl_property_type {
+    ($t:ty, $gtype:expr, $get:ident, $set:ident) => {
+        impl PropertyType for $t {
+
+            fn value_type() -> GType {
+                unsafe { GType::from_raw($gtype) }
+            }
+
+            unsafe fn from_value(value: &Value) -> $t {
+                ffi::$get(value.as_ptr()) as $t
+            }
+
+            unsafe fn to_value(&self, value: &mut Value) {
+                ffi::$set(value.as_mut_ptr(), *self as _)
+            }
+        }
+    }
+}
+
+impl_property_type!(i32, TYPE_INT, g_value_get_int, g_value_set_int);
+impl_property_type!(u32, TYPE_UINT, g_value_get_uint, g_value_set_uint);
+impl_property_type!(i64, TYPE_INT64, g_value_get_int64, g_value_set_int64);
+impl_property_type!(u64, TYPE_UINT64, g_value_get_uint64, g_value_set_uint64);
+impl_property_type!(f64, TYPE_DOUBLE, g_value_get_double, g_value_set_double);
+
+impl PropertyType for bool {
+
+    fn value_type() -> GType {
+        unsafe { GType::from_raw(TYPE_BOOLEAN) }
+    }
+
+    unsafe fn from_value(value: &Value) -> bool {
+        ffi::g_value_get_boolean(value.as_ptr()) != FALSE
+    }
+
+    unsafe fn to_value(&self, value: &mut Value) {
+        ffi::g_value_set_boolean(value.as_mut_ptr(),
+                                 if *self { TRUE } else { FALSE })
+    }
+}
+
+/// For string properties that cannot be unset. A NULL value,
+/// which the property should not have, is read as an empty string.
+impl PropertyType for String {
+
+    fn value_type() -> GType {
+        unsafe { GType::from_raw(TYPE_STRING) }
+    }
+
+    unsafe fn from_value(value: &Value) -> String {
+        Option::<String>::from_value(value).unwrap_or_default()
+    }
+
+    unsafe fn to_value(&self, value: &mut Value) {
+        Some(self.clone()).to_value(value)
+    }
+}
+
+/// String properties may be unset, which is represented as `None`.
+impl PropertyType for Option<String> {
+
+    fn value_type() -> GType {
+        unsafe { GType::from_raw(TYPE_STRING) }
+    }
+
+    unsafe fn from_value(value: &Value) -> Option<String> {
+        let s = ffi::g_value_get_string(value.as_ptr());
+        if s.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(s).to_string_lossy().into_owned())
+        }
+    }
+
+    unsafe fn to_value(&self, value: &mut Value) {
+        match *self {
+            Some(ref s) => {
+                // A C string ends at the first NUL
+                let s = CString::new(s.split('\0').next().unwrap()).unwrap();
+                ffi::g_value_set_string(value.as_mut_ptr(), s.as_ptr())
+            }
+            None => ffi::g_value_set_string(value.as_mut_ptr(), ptr::null())
+        }
+    }
+}
+
+impl Object {
+
+    fn type_name(&self) -> String {
+        type_name(self.instance_type())
+    }
+
+    fn class_ptr(&self) -> *mut ffi::GObjectClass {
+        unsafe {
+            let instance = self.as_mut_ptr() as *mut ffi::GTypeInstance;
+            (*instance).g_class as *mut ffi::GObjectClass
+        }
+    }
+
+    /// Looks up a property of the object's class by name.
+    pub fn find_property(&self, name: &str) -> Option<ParamSpec> {
+        let name = match CString::new(name) {
+            Ok(name) => name,
+            Err(_) => return None
+        };
+        unsafe {
+            let pspec = ffi::g_object_class_find_property(self.class_ptr(),
+                                                          name.as_ptr());
+            if pspec.is_null() {
+                None
+            } else {
+                Some(ParamSpec::from_raw_ref(pspec))
+            }
+        }
+    }
+
+    /// Lists the properties of the object's class,
+    /// including the inherited ones.
+    pub fn list_properties(&self) -> Vec<ParamSpec> {
+        unsafe {
+            let mut n: guint = 0;
+            let specs = ffi::g_object_class_list_properties(self.class_ptr(),
+                                                            &mut n);
+            // The array is NULL when the class has no properties
+            if specs.is_null() {
+                return Vec::new();
+            }
+            let list = slice::from_raw_parts(specs, n as usize).iter()
+                       .map(|&pspec| ParamSpec::from_raw_ref(pspec))
+                       .collect();
+            glib_ffi::g_free(specs as gpointer);
+            list
+        }
+    }
+
+    fn readable_property(&self, name: &str)
+                         -> Result<ParamSpec, PropertyError>
+    {
+        let pspec = self.find_property(name).ok_or_else(|| {
+            PropertyError::NotFound {
+                class: self.type_name(),
+                name: name.to_string()
+            }
+        })?;
+        if !pspec.get_flags().contains(flags::param::READABLE) {
+            return Err(PropertyError::NotReadable {
+                class: self.type_name(),
+                name: name.to_string()
+            });
+        }
+        Ok(pspec)
+    }
+
+    fn writable_property(&self, name: &str)
+                         -> Result<ParamSpec, PropertyError>
+    {
+        let pspec = self.find_property(name).ok_or_else(|| {
+            PropertyError::NotFound {
+                class: self.type_name(),
+                name: name.to_string()
+            }
+        })?;
+        let pflags = pspec.get_flags();
+        if !pflags.contains(flags::param::WRITABLE)
+           || pflags.contains(flags::param::CONSTRUCT_ONLY) {
+            return Err(PropertyError::NotWritable {
+                class: self.type_name(),
+                name: name.to_string()
+            });
+        }
+        Ok(pspec)
+    }
+
+    fn check_type(&self, pspec: &ParamSpec, src: ffi::GType, dest: ffi::GType)
+                  -> Result<(), PropertyError>
+    {
+        let compatible = unsafe {
+            ffi::g_value_type_transformable(src, dest) != FALSE
+        };
+        if compatible {
+            Ok(())
+        } else {
+            let (property_type, requested_type) =
+                if src == pspec.value_type().to_raw() {
+                    (src, dest)
+                } else {
+                    (dest, src)
+                };
+            Err(PropertyError::TypeMismatch {
+                class: self.type_name(),
+                name: pspec.get_name().to_string(),
+                property_type: type_name(property_type),
+                requested_type: type_name(requested_type),
+            })
+        }
+    }
+
+    /// Reads a property into a value of the property's type.
+    pub fn get_property_value(&self, name: &str)
+                              -> Result<Value, PropertyError>
+    {
+        let pspec = self.readable_property(name)?;
+        let mut value = Value::new(pspec.value_type());
+        unsafe {
+            ffi::g_object_get_property(self.as_mut_ptr(),
+                                       ffi::g_param_spec_get_name(pspec.raw),
+                                       value.as_mut_ptr());
+        }
+        Ok(value)
+    }
+
+    /// Reads a property, converting it to the type `T`.
+    pub fn get_property<T>(&self, name: &str) -> Result<T, PropertyError>
+        where T: PropertyType
+    {
+        let pspec = self.readable_property(name)?;
+        let value_type = T::value_type();
+        self.check_type(&pspec,
+                        pspec.value_type().to_raw(),
+                        value_type.to_raw())?;
+        let mut value = Value::new(value_type);
+        unsafe {
+            ffi::g_object_get_property(self.as_mut_ptr(),
+                                       ffi::g_param_spec_get_name(pspec.raw),
+                                       value.as_mut_ptr());
+            Ok(T::from_value(&value))
+        }
+    }
+
+    /// Sets a property from a value, which must be of a type
+    /// that can be converted to the property type.
+    pub fn set_property_value(&self, name: &str, value: &Value)
+                              -> Result<(), PropertyError>
+    {
+        let pspec = self.writable_property(name)?;
+        let value_type = unsafe { (*value.as_ptr()).g_type };
+        self.check_type(&pspec,
+                        value_type,
+                        pspec.value_type().to_raw())?;
+        {
+            // Handlers of "notify" are called synchronously
+            let _scope = glib::unwind::ResumeScope::enter();
+            unsafe {
+                let name = ffi::g_param_spec_get_name(pspec.raw);
+                ffi::g_object_set_property(self.as_mut_ptr(),
+                                           name,
+                                           value.as_ptr());
+            }
+        }
+        glib::unwind::resume_if_panicked();
+        Ok(())
+    }
+
+    /// Sets a property from a value of the type `T`.
+    pub fn set_property<T>(&self, name: &str, value: T)
+                           -> Result<(), PropertyError>
+        where T: PropertyType
+    {
+        let mut gvalue = Value::new(T::value_type());
+        unsafe { value.to_value(&mut gvalue) };
+        self.set_property_value(name, &gvalue)
+    }
+}
+
+/// Identifies a signal handler connected to an object.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SignalHandlerId(gulong);
+
+impl SignalHandlerId {
+
+    /// Wraps a handler ID returned by a `g_signal_connect*` function.
+    pub unsafe fn from_raw(id: gulong) -> SignalHandlerId {
+        SignalHandlerId(id)
+    }
+
+    pub fn to_raw(&self) -> gulong {
+        self.0
+    }
+}
+
+/// A guard keeping a signal handler connected to an object.
+///
+/// The handler is disconnected when the guard is dropped.
+/// The guard holds a reference to the object.
+pub struct SignalHandler {
+    instance: Ref<Object>,
+    id: SignalHandlerId,
+}
+
+impl SignalHandler {
+
+    pub fn new(instance: &Object, id: SignalHandlerId) -> SignalHandler {
+        SignalHandler { instance: Ref::new(instance), id: id }
+    }
+
+    pub fn id(&self) -> SignalHandlerId {
+        self.id
+    }
+
+    /// Blocks the handler from being invoked until `unblock` is called.
+    pub fn block(&self) {
+        self.instance.signal_handler_block(self.id)
+    }
+
+    pub fn unblock(&self) {
+        self.instance.signal_handler_unblock(self.id)
+    }
+
+    pub fn disconnect(self) {
+        // Disconnects in drop()
+    }
+
+    /// Releases the handler from the guard, leaving it connected
+    /// until it is disconnected by ID or the object is finalized.
+    pub fn detach(self) -> SignalHandlerId {
+        let id = self.id;
+        unsafe {
+            // Drop the object reference without running our drop()
+            let instance = ptr::read(&self.instance);
+            mem::forget(self);
+            drop(instance);
+        }
+        id
+    }
+}
+
+impl Drop for SignalHandler {
+    fn drop(&mut self) {
+        // The handler may have been disconnected by ID meanwhile
+        if self.instance.signal_handler_is_connected(self.id) {
+            self.instance.signal_handler_disconnect(self.id);
+        }
+    }
+}
+
+/// Errors in connecting to or emitting signals by name.
+#[derive(Clone, Debug)]
+pub enum SignalError {
+    /// The class has no signal with the given name,
+    /// or a detail was given for a signal that does not support it.
+    NotFound { class: String, name: String },
+    /// The number of arguments given for emission
+    /// does not match the signal's parameters.
+    ArgumentCount { name: String, expected: usize, actual: usize },
+    /// An argument given for emission is not compatible
+    /// with the type of the signal parameter.
+    ArgumentType {
+        name: String,
+        index: usize,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for SignalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SignalError::NotFound { ref class, ref name } => {
+                write!(f, "class {} has no signal \"{}\"", class, name)
+            }
+            SignalError::ArgumentCount { ref name, expected, actual } => {
+                write!(f, "signal \"{}\" takes {} arguments, {} given",
+                       name, expected, actual)
+            }
+            SignalError::ArgumentType { ref name, index,
+                                        ref expected, ref actual } => {
+                write!(f, "argument {} of signal \"{}\" must be {}, not {}",
+                       index, name, expected, actual)
+            }
+        }
+    }
+}
+
+impl Error for SignalError {
+    fn description(&self) -> &str {
+        match *self {
+            SignalError::NotFound { .. }      => "signal not found",
+            SignalError::ArgumentCount { .. } => "wrong number of arguments",
+            SignalError::ArgumentType { .. }  => "argument type mismatch",
+        }
+    }
+}
+
+// The flag that may be set in the parameter and return types
+// of a signal, G_SIGNAL_TYPE_STATIC_SCOPE
+const SIGNAL_TYPE_STATIC_SCOPE: ffi::GType = 1;
+
+const TYPE_NONE: ffi::GType = 1 << FUNDAMENTAL_SHIFT;
+
+mod callback {
+    use ffi;
+    use glib;
+    use grust::types::{gpointer, guint, FALSE};
+    use grust::value::Value;
+    use grust::wrap::Wrapper;
+    use std::slice;
+
+    use super::type_name;
+
+    pub extern "C" fn marshal_closure<F>(_closure: *mut ffi::GClosure,
+                                         return_value: *mut ffi::GValue,
+                                         n_param_values: guint,
+                                         param_values: *const ffi::GValue,
+                                         _invocation_hint: gpointer,
+                                         marshal_data: gpointer)
+        where F: Fn(&[Value]) -> Option<Value>
+    {
+        let cb: &F = unsafe { &*(marshal_data as *const F) };
+        let args = unsafe {
+            // Value is a transparent wrapper of GValue
+            slice::from_raw_parts(param_values as *const Value,
+                                  n_param_values as usize)
+        };
+        // Signals can be emitted by any code calling into C,
+        // so the panic may have nowhere to be resumed
+        glib::unwind::catch_panic_or_abort(|| {
+            let ret = cb(args);
+            if return_value.is_null() {
+                return;
+            }
+            if let Some(value) = ret {
+                let ok = unsafe {
+                    ffi::g_value_transform(value.as_ptr(), return_value)
+                };
+                if ok == FALSE {
+                    let (src, dest) = unsafe {
+                        ((*value.as_ptr()).g_type, (*return_value).g_type)
+                    };
+                    panic!("signal handler returned a value of type {}, \
+                            which cannot be converted to {}",
+                           type_name(src), type_name(dest));
+                }
+            }
+        });
+    }
+
+    pub extern "C" fn finalize_closure<F>(data: gpointer,
+                                          _closure: *mut ffi::GClosure)
+    {
+        let _: Box<F> = unsafe { Box::from_raw(data as *mut F) };
+    }
+}
+
+/// Borrows the object held by a value as the type `T`.
+///
+/// This is meant for the arguments passed to closures connected
+/// with `Object::connect`, the first of which is the instance
+/// emitting the signal.
+///
+/// # Safety
+///
+/// The value must hold a non-null instance of `T`.
+pub unsafe fn value_as_object<T>(value: &Value) -> &T
+    where T: object::ObjectType + Wrapper
+{
+    let raw = ffi::g_value_peek_pointer(value.as_ptr());
+    wrap::from_raw(raw as *const T::Raw)
+}
+
+impl Object {
+
+    fn instance_type(&self) -> ffi::GType {
+        unsafe {
+            let instance = self.as_mut_ptr() as *mut ffi::GTypeInstance;
+            (*(*instance).g_class).g_type
+        }
+    }
+
+    fn parse_signal(&self, name: &str)
+                    -> Result<(guint, glib_ffi::GQuark), SignalError>
+    {
+        let not_found = || {
+            SignalError::NotFound {
+                class: self.type_name(),
+                name: name.to_string()
+            }
+        };
+        let c_name = CString::new(name).map_err(|_| not_found())?;
+        let mut signal_id: guint = 0;
+        let mut detail: glib_ffi::GQuark = 0;
+        let found = unsafe {
+            ffi::g_signal_parse_name(c_name.as_ptr(),
+                                     self.instance_type(),
+                                     &mut signal_id,
+                                     &mut detail,
+                                     TRUE)
+        };
+        if found == FALSE {
+            return Err(not_found());
+        }
+        Ok((signal_id, detail))
+    }
+
+    /// Connects a closure to a signal given by name, which may
+    /// include a detail, as in `"notify::name"`.
+    ///
+    /// The closure receives the signal arguments as values,
+    /// the first being the instance emitting the signal.
+    /// The value it returns is converted to the return type
+    /// of the signal, if the signal has one.
+    /// Signals may be emitted in any thread, hence the closure must
+    /// be `Send` and `Sync`. It is freed when the handler
+    /// is disconnected or the object is finalized.
+    ///
+    /// A panic in the closure is resumed by `emit_by_name`, property
+    /// setters, or the main loop or executor running in the thread.
+    /// If the signal is emitted in a thread where none of these
+    /// is active, such as a GIO worker thread, the process is aborted.
+    pub fn connect<F>(&self, signal: &str, callback: F)
+                      -> Result<SignalHandlerId, SignalError>
+        where F: Fn(&[Value]) -> Option<Value>,
+              F: Send + Sync + 'static
+    {
+        let (signal_id, detail) = self.parse_signal(signal)?;
+        unsafe {
+            let data = Box::into_raw(Box::new(callback)) as gpointer;
+            let closure = ffi::g_closure_new_simple(
+                            mem::size_of::<ffi::GClosure>() as guint,
+                            ptr::null_mut());
+            ffi::g_closure_set_meta_marshal(
+                    closure, data,
+                    Some(callback::marshal_closure::<F>));
+            ffi::g_closure_add_finalize_notifier(
+                    closure, data,
+                    Some(callback::finalize_closure::<F>));
+            let id = ffi::g_signal_connect_closure_by_id(
+                            self.as_mut_ptr() as gpointer,
+                            signal_id, detail, closure, FALSE);
+            Ok(SignalHandlerId(id))
+        }
+    }
+
+    /// Connects a closure to the `notify` signal, which is emitted
+    /// when a property of the object changes.
+    ///
+    /// If `property` is given, only changes to that property
+    /// are notified.
+    pub fn connect_notify<F>(&self, property: Option<&str>, callback: F)
+                             -> Result<SignalHandlerId, SignalError>
+        where F: Fn(&Object, &ParamSpec),
+              F: Send + Sync + 'static
+    {
+        let signal = match property {
+            Some(name) => {
+                // Any detail would be accepted for "notify"
+                if self.find_property(name).is_none() {
+                    return Err(SignalError::NotFound {
+                        class: self.type_name(),
+                        name: format!("notify::{}", name)
+                    });
+                }
+                format!("notify::{}", name)
+            }
+            None => "notify".to_string()
+        };
+        self.connect(&signal, move |args| {
+            unsafe {
+                let obj = value_as_object::<Object>(&args[0]);
+                let pspec = ffi::g_value_get_param(args[1].as_ptr());
+                callback(obj, &ParamSpec::from_raw_ref(pspec));
+            }
+            None
+        })
+    }
+
+    /// Emits a signal given by name, with the arguments following
+    /// the instance.
+    ///
+    /// Returns the value returned by the handlers, or `None` if
+    /// the signal has no return type. Panics in handlers connected
+    /// with `connect` are resumed after the emission is complete.
+    pub fn emit_by_name(&self, signal: &str, args: &[&Value])
+                        -> Result<Option<Value>, SignalError>
+    {
+        let (signal_id, detail) = self.parse_signal(signal)?;
+        let mut query: ffi::GSignalQuery = unsafe { mem::zeroed() };
+        unsafe { ffi::g_signal_query(signal_id, &mut query) };
+        // GLib leaves param_types NULL for signals without parameters
+        let param_types: &[ffi::GType] =
+            if query.n_params == 0 || query.param_types.is_null() {
+                &[]
+            } else {
+                unsafe {
+                    slice::from_raw_parts(query.param_types,
+                                          query.n_params as usize)
+                }
+            };
+        if args.len() != param_types.len() {
+            return Err(SignalError::ArgumentCount {
+                name: signal.to_string(),
+                expected: param_types.len(),
+                actual: args.len()
+            });
+        }
+        for (i, (arg, &param_type)) in args.iter()
+                                           .zip(param_types)
+                                           .enumerate() {
+            let param_type = param_type & !SIGNAL_TYPE_STATIC_SCOPE;
+            let arg_type = unsafe { (*arg.as_ptr()).g_type };
+            let compatible = unsafe {
+                ffi::g_value_type_compatible(arg_type, param_type) != FALSE
+            };
+            if !compatible {
+                return Err(SignalError::ArgumentType {
+                    name: signal.to_string(),
+                    index: i,
+                    expected: type_name(param_type),
+                    actual: type_name(arg_type)
+                });
+            }
+        }
+
+        // The instance and arguments are passed in a contiguous array
+        let mut values = Vec::with_capacity(args.len() + 1);
+        let mut instance = Value::new(unsafe {
+            GType::from_raw(self.instance_type())
+        });
+        unsafe {
+            ffi::g_value_set_object(instance.as_mut_ptr(),
+                                    self.as_mut_ptr() as gpointer);
+        }
+        values.push(instance);
+        values.extend(args.iter().map(|&arg| arg.clone()));
+
+        let return_type = query.return_type & !SIGNAL_TYPE_STATIC_SCOPE;
+        let mut ret = if return_type == TYPE_NONE {
+            None
+        } else {
+            Some(Value::new(unsafe { GType::from_raw(return_type) }))
+        };
+        {
+            let _scope = glib::unwind::ResumeScope::enter();
+            unsafe {
+                let ret_ptr = match ret {
+                    Some(ref mut value) => value.as_mut_ptr(),
+                    None => ptr::null_mut()
+                };
+                ffi::g_signal_emitv(values.as_ptr() as *const ffi::GValue,
+                                    signal_id, detail, ret_ptr);
+            }
+        }
+        glib::unwind::resume_if_panicked();
+        Ok(ret)
+    }
+
+    /// Blocks a handler from being invoked until it is unblocked.
+    ///
+    /// Blocking is counted; a handler blocked several times needs
+    /// to be unblocked as many times.
+    pub fn signal_handler_block(&self, id: SignalHandlerId) {
+        unsafe {
+            ffi::g_signal_handler_block(self.as_mut_ptr() as gpointer, id.0);
+        }
+    }
+
+    pub fn signal_handler_unblock(&self, id: SignalHandlerId) {
+        unsafe {
+            ffi::g_signal_handler_unblock(self.as_mut_ptr() as gpointer,
+                                          id.0);
+        }
+    }
+
+    pub fn signal_handler_disconnect(&self, id: SignalHandlerId) {
+        unsafe {
+            ffi::g_signal_handler_disconnect(self.as_mut_ptr() as gpointer,
+                                             id.0);
+        }
+    }
+
+    pub fn signal_handler_is_connected(&self, id: SignalHandlerId) -> bool {
+        unsafe {
+            ffi::g_signal_handler_is_connected(self.as_mut_ptr() as gpointer,
+                                               id.0) != FALSE
+        }
+    }
+}
+
+pub mod upcast {
+    //! Conversion of owned references to ancestor types.
+
+    use grust::object::{ObjectType, Upcast};
+    use grust::refcount::Ref;
+    use grust::wrap::Wrapper;
+
+    use std::mem;
+
+    /// Upcasts of owned references.
+    ///
+    /// This stands in for `Into<Ref<U>>`, which cannot be implemented
+    /// for `Ref<T>` because it would overlap with the reflexive
+    /// `From` impl in the standard library.
+    pub trait IntoUpcast<T> where T: ObjectType + Wrapper {
+
+        /// Converts the reference into a reference to an ancestor type
+        /// or an implemented interface. The reference count is passed
+        /// on rather than incremented.
+        fn into_upcast<U>(self) -> Ref<U>
+            where T: Upcast<U>,
+                  U: ObjectType + Wrapper;
+    }
+
+    impl<T> IntoUpcast<T> for Ref<T> where T: ObjectType + Wrapper {
+
+        fn into_upcast<U>(self) -> Ref<U>
+            where T: Upcast<U>,
+                  U: ObjectType + Wrapper
+        {
+            unsafe {
+                let raw = (*self).upcast().as_mut_ptr();
+                mem::forget(self);
+                Ref::from_raw(raw)
+            }
+        }
+    }
+}
+
+pub mod downcast {
+    //! Casts checked against the runtime type of an instance.
+    //!
+    //! Unlike `grust::object::cast`, which panics when the instance
+    //! is not of the requested type, these return the failure
+    //! to the caller, for instances whose concrete type is only known
+    //! at run time, such as source objects of asynchronous operations.
+
+    use ffi;
+    use super::type_name;
+
+    use grust::gtype::GType;
+    use grust::object::ObjectType;
+    use grust::refcount::Ref;
+    use grust::wrap;
+    use grust::wrap::Wrapper;
+
+    use std::error::Error;
+    use std::fmt;
+    use std::mem;
+
+    /// The error returned when an instance is not of the requested type.
+    #[derive(Copy, Clone)]
+    pub struct CastError {
+        expected: ffi::GType,
+        actual: ffi::GType,
+    }
+
+    impl CastError {
+
+        /// The type that the instance was requested to be cast to.
+        pub fn expected_type(&self) -> GType {
+            unsafe { GType::from_raw(self.expected) }
+        }
+
+        /// The runtime type of the instance.
+        pub fn actual_type(&self) -> GType {
+            unsafe { GType::from_raw(self.actual) }
+        }
+    }
+
+    impl fmt::Display for CastError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "instance of {} is not a {}",
+                   type_name(self.actual), type_name(self.expected))
+        }
+    }
+
+    impl fmt::Debug for CastError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.debug_struct("CastError")
+             .field("expected", &type_name(self.expected))
+             .field("actual", &type_name(self.actual))
+             .finish()
+        }
+    }
+
+    impl Error for CastError {
+        fn description(&self) -> &str {
+            "instance is not of the requested type"
+        }
+    }
+
+    fn check<T, U>(instance: &T) -> Result<(), CastError>
+        where T: ObjectType + Wrapper,
+              U: ObjectType + Wrapper
+    {
+        let expected = U::get_type().to_raw();
+        unsafe {
+            let raw = instance.as_mut_ptr() as *mut ffi::GTypeInstance;
+            if ffi::g_type_check_instance_is_a(raw, expected) != 0 {
+                Ok(())
+            } else {
+                Err(CastError {
+                    expected: expected,
+                    actual: (*(*raw).g_class).g_type,
+                })
+            }
+        }
+    }
+
+    /// Checked casts of borrowed instances.
+    pub trait Downcast {
+
+        /// Casts the instance to type `U`, if it is an instance of `U`
+        /// or of a type derived from it.
+        fn try_cast<U>(&self) -> Result<&U, CastError>
+            where U: ObjectType + Wrapper;
+
+        /// Like `try_cast`, discarding the error.
+        fn downcast_ref<U>(&self) -> Option<&U>
+            where U: ObjectType + Wrapper
+        {
+            self.try_cast().ok()
+        }
+    }
+
+    impl<T> Downcast for T where T: ObjectType + Wrapper {
+
+        fn try_cast<U>(&self) -> Result<&U, CastError>
+            where U: ObjectType + Wrapper
+        {
+            check::<T, U>(self).map(|()| unsafe {
+                wrap::from_raw(self.as_mut_ptr() as *const U::Raw)
+            })
+        }
+    }
+
+    /// Checked casts of owned references.
+    pub trait DowncastRef<T> where T: ObjectType + Wrapper {
+
+        /// Converts the reference into a reference to `U`, keeping
+        /// the reference count. If the instance is not of type `U`,
+        /// the original reference is given back.
+        fn downcast<U>(self) -> Result<Ref<U>, Ref<T>>
+            where U: ObjectType + Wrapper;
+    }
+
+    impl<T> DowncastRef<T> for Ref<T> where T: ObjectType + Wrapper {
+
+        fn downcast<U>(self) -> Result<Ref<U>, Ref<T>>
+            where U: ObjectType + Wrapper
+        {
+            if check::<T, U>(&*self).is_err() {
+                return Err(self);
+            }
+            unsafe {
+                let raw = self.as_mut_ptr() as *mut U::Raw;
+                mem::forget(self);
+                Ok(Ref::from_raw(raw))
+            }
+        }
+    }
+}
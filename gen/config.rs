@@ -0,0 +1,127 @@
+// This file is part of Grust, GObject introspection bindings for Rust
+//
+// Copyright (C) 2016  Mikhail Zabaluev <mikhail.zabaluev@gmail.com>
+//
+// This library is free software; you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation; either
+// version 2.1 of the License, or (at your option) any later version.
+//
+// This library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public
+// License along with this library; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+
+//! Per-crate settings describing which parts of each GIR namespace
+//! are emitted into the crates under `fake-gen`.
+
+pub struct CrateConfig {
+    /// GIR namespace name.
+    pub namespace: &'static str,
+    /// GIR namespace version.
+    pub version: &'static str,
+    /// Name of the generated library crate.
+    pub crate_name: &'static str,
+    /// Path of the crate source, relative to the `fake-gen` directory.
+    pub source: &'static str,
+    /// Names of the classes, interfaces and records to emit.
+    pub types: &'static [&'static str],
+    /// Types whose instances can be safely shared between threads.
+    pub thread_safe: &'static [&'static str],
+    /// Names of the enumerations to emit.
+    pub enums: &'static [&'static str],
+    /// Names of the bit field types to emit.
+    pub flags: &'static [&'static str],
+}
+
+/// Namespaces that generated crates can depend upon, with the names
+/// by which the wrapper crate and the sys crate are imported.
+pub const DEPENDENCIES: &[(&str, &str, &str)] = &[
+    ("GLib",    "glib",    "glib_ffi"),
+    ("GObject", "gobject", "gobject_ffi"),
+    ("Gio",     "gio",     "gio_ffi"),
+];
+
+pub const GLIB: CrateConfig = CrateConfig {
+    namespace: "GLib",
+    version: "2.0",
+    crate_name: "grust_glib_2_0",
+    source: "glib/glib.rs",
+    types: &[],
+    thread_safe: &[],
+    enums: &["SeekType"],
+    flags: &[],
+};
+
+pub const GOBJECT: CrateConfig = CrateConfig {
+    namespace: "GObject",
+    version: "2.0",
+    crate_name: "grust_gobject_2_0",
+    source: "gobject/gobject.rs",
+    types: &["TypeInstance", "Object"],
+    thread_safe: &[],
+    enums: &[],
+    flags: &["ParamFlags"],
+};
+
+pub const GIO: CrateConfig = CrateConfig {
+    namespace: "Gio",
+    version: "2.0",
+    crate_name: "grust_gio_2_0",
+    source: "gio/gio.rs",
+    types: &[
+        "AsyncResult",
+        "File",
+        "Cancellable",
+        "InputStream",
+        "FileInputStream",
+        "OutputStream",
+        "FileOutputStream",
+        "FileInfo",
+        "FileEnumerator",
+        "FileMonitor",
+        "IOStream",
+        "FileIOStream",
+        "MemoryInputStream",
+        "MemoryOutputStream",
+        "FilterInputStream",
+        "BufferedInputStream",
+        "DataInputStream",
+        "FilterOutputStream",
+        "BufferedOutputStream",
+        "DataOutputStream",
+        "Converter",
+        "ZlibCompressor",
+        "ZlibDecompressor",
+        "CharsetConverter",
+        "ConverterInputStream",
+        "ConverterOutputStream",
+        "Seekable",
+    ],
+    thread_safe: &["Cancellable"],
+    enums: &[
+        "IOErrorEnum",
+        "FileType",
+        "FileAttributeType",
+        "FileMonitorEvent",
+        "DataStreamByteOrder",
+        "DataStreamNewlineType",
+        "ZlibCompressorFormat",
+        "ConverterResult",
+    ],
+    flags: &[
+        "ConverterFlags",
+        "FileAttributeInfoFlags",
+        "FileCopyFlags",
+        "FileCreateFlags",
+        "FileMonitorFlags",
+        "FileQueryInfoFlags",
+        "OutputStreamSpliceFlags",
+    ],
+};
+
+pub const ALL: &[&CrateConfig] = &[&GLIB, &GOBJECT, &GIO];
@@ -0,0 +1,834 @@
+// This file is part of Grust, GObject introspection bindings for Rust
+//
+// Copyright (C) 2016  Mikhail Zabaluev <mikhail.zabaluev@gmail.com>
+//
+// This library is free software; you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation; either
+// version 2.1 of the License, or (at your option) any later version.
+//
+// This library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public
+// License along with this library; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+
+//! Emission of Rust source for a configured GIR namespace.
+//!
+//! Each piece of output is produced as a separate item, so that
+//! the golden test can check the items individually against the
+//! hand-edited crates under `fake-gen`.
+
+use crate::config;
+use crate::config::CrateConfig;
+use crate::gir;
+use crate::gir::{Enumeration, Repository, Type, TypeKind};
+
+use std::fmt::Write;
+
+const LICENSE_HEADER: &str = "\
+// This file is part of Grust, GObject introspection bindings for Rust
+//
+// This library is free software; you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation; either
+// version 2.1 of the License, or (at your option) any later version.
+//
+// This library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public
+// License along with this library; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+";
+
+/// Generated items, grouped in the order they appear in a crate.
+pub struct Output {
+    pub wrappers: Vec<String>,
+    pub enums: Vec<String>,
+    pub flags: Vec<String>,
+    pub flags_reexports: Vec<String>,
+    pub cast_traits: Vec<String>,
+    pub object_types: Vec<String>,
+    pub upcasts: Vec<String>,
+    pub as_refs: Vec<String>,
+    pub properties: Vec<String>,
+    pub signals: Vec<String>,
+}
+
+impl Output {
+
+    /// Returns the groups of items with their names,
+    /// in the order of emission.
+    #[cfg(test)]
+    pub fn sections(&self) -> Vec<(&'static str, &[String])> {
+        vec![
+            ("wrappers", &self.wrappers),
+            ("enums", &self.enums),
+            ("flags", &self.flags),
+            ("flags re-exports", &self.flags_reexports),
+            ("cast traits", &self.cast_traits),
+            ("object types", &self.object_types),
+            ("upcasts", &self.upcasts),
+            ("AsRef implementations", &self.as_refs),
+            ("properties", &self.properties),
+            ("signals", &self.signals),
+        ]
+    }
+}
+
+pub struct Generator<'a> {
+    repo: &'a Repository,
+    conf: &'a CrateConfig,
+}
+
+fn dependency(ns: &str) -> (&'static str, &'static str) {
+    match config::DEPENDENCIES.iter().find(|d| d.0 == ns) {
+        Some(&(_, krate, ffi)) => (krate, ffi),
+        None => panic!("namespace {} is not known as a dependency", ns)
+    }
+}
+
+/// Converts a CamelCase type name to snake_case,
+/// keeping acronyms together: `IOErrorEnum` becomes `io_error_enum`.
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && chars[i - 1].is_lowercase();
+            let acronym_end = i > 0 && chars[i - 1].is_uppercase()
+                              && chars.get(i + 1)
+                                      .is_some_and(|c| c.is_lowercase());
+            if after_lower || acronym_end {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Converts a snake_case GIR member name to a CamelCase variant name.
+pub fn camel_case(name: &str) -> String {
+    let mut out = String::new();
+    for word in name.split('_') {
+        let mut chars = word.chars();
+        if let Some(c) = chars.next() {
+            out.extend(c.to_uppercase());
+            out.extend(chars);
+        }
+    }
+    out
+}
+
+/// Maps the value type of a GIR property to the Rust type
+/// of the typed accessors. Nullable strings are mapped to `Option`.
+fn property_type(p: &gir::Property) -> Option<&'static str> {
+    match p.type_name.as_ref().map(|s| &s[..]) {
+        Some("gboolean") => Some("bool"),
+        Some("gint")     => Some("i32"),
+        Some("guint")    => Some("u32"),
+        Some("gint64")   => Some("i64"),
+        Some("guint64")  => Some("u64"),
+        Some("gdouble")  => Some("f64"),
+        Some("utf8") if p.nullable => Some("Option<String>"),
+        Some("utf8")     => Some("String"),
+        _                => None
+    }
+}
+
+/// Returns whether a `connect_*` method is generated for the signal:
+/// it must have no parameters besides the instance and no return value.
+pub fn emits_signal(signal: &gir::Signal) -> bool {
+    signal.parameters.is_empty()
+        && signal.return_value.type_name.as_ref()
+                 .is_none_or(|t| t == "none")
+}
+
+fn sys_crate_name(ns: &str, version: &str) -> String {
+    format!("{}_{}_sys", ns.to_lowercase(), version.replace(".", "_"))
+}
+
+impl<'a> Generator<'a> {
+
+    pub fn new(repo: &'a Repository, conf: &'a CrateConfig) -> Generator<'a> {
+        Generator { repo, conf }
+    }
+
+    fn namespace(&self) -> &'a gir::Namespace {
+        self.repo.namespace(self.conf.namespace)
+    }
+
+    fn lookup_type(&self, name: &str) -> &'a Type {
+        match self.namespace().find_type(name) {
+            Some(t) => t,
+            None => panic!("type {}.{} not found", self.conf.namespace, name)
+        }
+    }
+
+    fn is_local(&self, ns: &str) -> bool {
+        ns == self.conf.namespace
+    }
+
+    /// Path to a type from the crate root.
+    fn type_path(&self, ns: &str, t: &Type) -> String {
+        if self.is_local(ns) {
+            t.name.clone()
+        } else {
+            format!("{}::{}", dependency(ns).0, t.name)
+        }
+    }
+
+    /// Path to a type from within the `cast` module.
+    fn cast_type_path(&self, ns: &str, t: &Type) -> String {
+        if self.is_local(ns) {
+            format!("super::{}", t.name)
+        } else {
+            format!("{}::{}", dependency(ns).0, t.name)
+        }
+    }
+
+    fn cast_trait_path(&self, ns: &str, t: &Type, from_root: bool) -> String {
+        if self.is_local(ns) {
+            if from_root {
+                format!("cast::As{}", t.name)
+            } else {
+                format!("As{}", t.name)
+            }
+        } else {
+            format!("{}::cast::As{}", dependency(ns).0, t.name)
+        }
+    }
+
+    fn cast_method(t: &Type) -> String {
+        match t.symbol_prefix {
+            Some(ref prefix) => format!("as_{}", prefix),
+            None => format!("as_{}", snake_case(&t.name))
+        }
+    }
+
+    /// Lists the ancestors of a class, from the immediate parent
+    /// up to the root of the hierarchy.
+    fn ancestors(&self, t: &'a Type) -> Vec<(&'a str, &'a Type)> {
+        let mut ancestors = Vec::new();
+        let mut ns: &'a str = self.conf.namespace;
+        let mut cur = t;
+        while let Some(ref parent) = cur.parent {
+            let (parent_ns, parent_type) = self.repo.resolve(ns, parent);
+            ancestors.push((parent_ns, parent_type));
+            ns = parent_ns;
+            cur = parent_type;
+        }
+        ancestors
+    }
+
+    /// Lists the interfaces implemented by a class, leaving out
+    /// those that are not emitted in this crate or its dependencies.
+    fn interfaces(&self, t: &'a Type) -> Vec<(&'a str, &'a Type)> {
+        let ns: &'a str = self.conf.namespace;
+        t.implements.iter().filter(|name| {
+            let (iface_ns, iface_name) = match name.find('.') {
+                Some(pos) => (&name[.. pos], &name[pos + 1 ..]),
+                None      => (ns, &name[..])
+            };
+            config::ALL.iter().any(|c| {
+                c.namespace == iface_ns
+                    && c.types.contains(&iface_name)
+            })
+        }).map(|name| self.repo.resolve(ns, name)).collect()
+    }
+
+    fn emit_wrapper(&self, t: &Type, out: &mut Vec<String>) {
+        out.push(format!("\
+#[repr(C)]
+pub struct {name} {{
+    raw: ffi::{c_type}
+}}
+", name = t.name, c_type = t.c_type));
+        if self.conf.thread_safe.iter().any(|n| *n == t.name) {
+            out.push(format!("\
+unsafe impl Send for {name} {{ }}
+unsafe impl Sync for {name} {{ }}
+", name = t.name));
+        }
+        out.push(format!("\
+unsafe impl wrap::Wrapper for {name} {{
+    type Raw = ffi::{c_type};
+}}
+", name = t.name, c_type = t.c_type));
+    }
+
+    fn emit_cast_trait(&self, t: &'a Type, out: &mut Vec<String>) {
+        let method = Generator::cast_method(t);
+        let self_path = format!("super::{}", t.name);
+        let mut bounds = vec![self_path.clone()];
+        let supertrait = match t.kind {
+            TypeKind::Class => {
+                let ancestors = self.ancestors(t);
+                for &(ns, a) in ancestors.iter() {
+                    bounds.push(self.cast_type_path(ns, a));
+                }
+                ancestors.first().map(|&(ns, p)| {
+                    format!(" : {}", self.cast_trait_path(ns, p, false))
+                })
+            }
+            _ => None
+        };
+        let mut s = String::new();
+        write!(s, "\
+pub trait As{name}{supertrait} {{
+    fn {method}(&self) -> &{path};
+}}
+
+",
+            name = t.name,
+            supertrait = supertrait.unwrap_or(String::new()),
+            method = method,
+            path = self_path).unwrap();
+        if bounds.len() == 1 {
+            writeln!(s, "impl<T> As{} for T where T: object::Upcast<{}> {{",
+                     t.name, self_path).unwrap();
+        } else {
+            writeln!(s, "impl<T> As{} for T", t.name).unwrap();
+            for (i, bound) in bounds.iter().enumerate() {
+                let lead = if i == 0 { "    where" } else { "         " };
+                let sep = if i + 1 < bounds.len() { "," } else { "" };
+                writeln!(s, "{} T: object::Upcast<{}>{}", lead, bound, sep)
+                    .unwrap();
+            }
+            s.push('{');
+        }
+        write!(s, "
+    #[inline]
+    fn {method}(&self) -> &{path} {{
+        self.upcast()
+    }}
+}}
+", method = method, path = self_path).unwrap();
+        out.push(s);
+    }
+
+    fn emit_object_type(&self, t: &Type, out: &mut Vec<String>) {
+        let get_type = match t.get_type {
+            Some(ref f) => f,
+            None => return
+        };
+        out.push(format!("\
+unsafe impl object::ObjectType for {name} {{
+    fn get_type() -> GType {{
+        unsafe {{
+            GType::from_raw(ffi::{get_type}())
+        }}
+    }}
+}}
+", name = t.name, get_type = get_type));
+    }
+
+    fn emit_upcasts(&self, t: &'a Type, out: &mut Vec<String>) {
+        self.emit_class_upcasts(t, out);
+        // Interface instances are the instances of implementing classes,
+        // so the pointer is cast to the interface type
+        for (ns, iface) in self.interfaces(t) {
+            let ffi = if self.is_local(ns) {
+                "ffi"
+            } else {
+                dependency(ns).1
+            };
+            out.push(format!("\
+impl object::Upcast<{iface}> for {name} {{
+
+    #[inline]
+    fn upcast(&self) -> &{iface} {{
+        unsafe {{
+            wrap::from_raw(self as *const {name} as *const {ffi}::{c_type})
+        }}
+    }}
+}}
+",
+                iface = self.type_path(ns, iface),
+                name = t.name,
+                ffi = ffi,
+                c_type = iface.c_type));
+        }
+    }
+
+    fn emit_class_upcasts(&self, t: &'a Type, out: &mut Vec<String>) {
+        let ancestors = self.ancestors(t);
+        let (parent_ns, parent) = match ancestors.first() {
+            Some(&p) => p,
+            None => return
+        };
+        let parent_path = self.type_path(parent_ns, parent);
+        // The instance structures of some classes are private,
+        // so the pointer has to be cast to the parent type
+        let parent_ref = match t.first_field {
+            Some(ref field) => format!("&self.raw.{}", field),
+            None => {
+                let ffi = if self.is_local(parent_ns) {
+                    "ffi"
+                } else {
+                    dependency(parent_ns).1
+                };
+                format!("self as *const {} as *const {}::{}",
+                        t.name, ffi, parent.c_type)
+            }
+        };
+        out.push(format!("\
+impl object::Upcast<{parent}> for {name} {{
+
+    #[inline]
+    fn upcast(&self) -> &{parent} {{
+        unsafe {{
+            wrap::from_raw({parent_ref})
+        }}
+    }}
+}}
+", parent = parent_path, name = t.name, parent_ref = parent_ref));
+        for &(ns, a) in ancestors[1..].iter() {
+            out.push(format!("\
+impl object::Upcast<{ancestor}> for {name} {{
+
+    #[inline]
+    fn upcast(&self) -> &{ancestor} {{
+        use {cast_trait};
+        self.{method}().upcast()
+    }}
+}}
+",
+                ancestor = self.type_path(ns, a),
+                name = t.name,
+                cast_trait = self.cast_trait_path(parent_ns, parent, true),
+                method = Generator::cast_method(parent)));
+        }
+    }
+
+    /// Emits `AsRef` conversions to the type itself and to every type
+    /// it can be upcast to, so that generic code can accept any
+    /// of the derived types.
+    fn emit_as_refs(&self, t: &'a Type, out: &mut Vec<String>) {
+        out.push(format!("\
+impl AsRef<{name}> for {name} {{
+
+    #[inline]
+    fn as_ref(&self) -> &{name} {{
+        self
+    }}
+}}
+", name = t.name));
+        let mut targets = Vec::new();
+        if t.kind == TypeKind::Class {
+            targets.extend(self.ancestors(t));
+            targets.extend(self.interfaces(t));
+        }
+        for (ns, target) in targets {
+            out.push(format!("\
+impl AsRef<{target}> for {name} {{
+
+    #[inline]
+    fn as_ref(&self) -> &{target} {{
+        use {cast_trait};
+        self.{method}()
+    }}
+}}
+",
+                target = self.type_path(ns, target),
+                name = t.name,
+                cast_trait = self.cast_trait_path(ns, target, true),
+                method = Generator::cast_method(target)));
+        }
+    }
+
+    /// Emits typed accessors for the properties of a class, going
+    /// through the dynamic property methods of `GObject.Object`.
+    /// Properties of types not listed in `property_type` are skipped.
+    fn emit_properties(&self, t: &'a Type, out: &mut Vec<String>) {
+        let (object_ns, object) = self.repo.resolve(self.conf.namespace,
+                                                    "GObject.Object");
+        let cast_trait = self.cast_trait_path(object_ns, object, true);
+        let method = Generator::cast_method(object);
+        let mut s = String::new();
+        for p in t.properties.iter() {
+            let rust_type = match property_type(p) {
+                Some(rust_type) => rust_type,
+                None => continue
+            };
+            let prop = p.name.replace("-", "_");
+            if p.readable {
+                write!(s, "
+    pub fn prop_{prop}(&self) -> {rust_type} {{
+        use {cast_trait};
+        self.{method}().get_property(\"{name}\").unwrap()
+    }}
+",
+                    prop = prop, rust_type = rust_type, name = p.name,
+                    cast_trait = cast_trait, method = method).unwrap();
+            }
+            if p.writable && !p.construct_only {
+                write!(s, "
+    pub fn set_prop_{prop}(&self, value: {rust_type}) {{
+        use {cast_trait};
+        self.{method}().set_property(\"{name}\", value).unwrap()
+    }}
+",
+                    prop = prop, rust_type = rust_type, name = p.name,
+                    cast_trait = cast_trait, method = method).unwrap();
+            }
+        }
+        if !s.is_empty() {
+            out.push(format!("impl {} {{\n{}}}\n", t.name, s));
+        }
+    }
+
+    /// Emits typed `connect_*` methods for the signals of a class,
+    /// going through `Object::connect`. Only signals without parameters
+    /// and return values are covered; others need hand-written methods.
+    fn emit_signals(&self, t: &'a Type, out: &mut Vec<String>) {
+        let (object_ns, object) = self.repo.resolve(self.conf.namespace,
+                                                    "GObject.Object");
+        let gobject = if self.is_local(object_ns) {
+            String::new()
+        } else {
+            format!("{}::", dependency(object_ns).0)
+        };
+        let mut s = String::new();
+        for signal in t.signals.iter().filter(|s| emits_signal(s)) {
+            write!(s, "
+    /// Connects a closure to the `{name}` signal.
+    pub fn connect_{method}<F>(&self, callback: F) -> {gobject}SignalHandler
+        where F: Fn(&{class}) + Send + Sync + 'static
+    {{
+        use {cast_trait};
+        let object = self.{cast_method}();
+        let id = object.connect(\"{name}\", move |args| {{
+            callback(unsafe {{ {gobject}value_as_object(&args[0]) }});
+            None
+        }}).unwrap();
+        {gobject}SignalHandler::new(object, id)
+    }}
+",
+                name = signal.name, method = signal.name.replace("-", "_"),
+                class = t.name, gobject = gobject,
+                cast_trait = self.cast_trait_path(object_ns, object, true),
+                cast_method = Generator::cast_method(object)).unwrap();
+        }
+        if !s.is_empty() {
+            out.push(format!("impl {} {{\n{}}}\n", t.name, s));
+        }
+    }
+
+    fn emit_enum(&self, e: &Enumeration, out: &mut Vec<String>) {
+        // Rust does not allow aliased discriminants, so only the first
+        // member with any given value gets a variant.
+        let mut members = Vec::new();
+        for m in e.members.iter() {
+            if !members.iter().any(|p: &&gir::Member| p.value == m.value) {
+                members.push(m);
+            }
+        }
+
+        let mut s = String::new();
+        write!(s, "\
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub enum {} {{
+", e.name).unwrap();
+        for m in members.iter() {
+            writeln!(s, "    {} = {},", camel_case(&m.name), m.value).unwrap();
+        }
+        s.push_str("}\n");
+        out.push(s);
+
+        let mut s = String::new();
+        write!(s, "\
+impl enumeration::IntrospectedEnum for {name} {{
+
+    fn from_int(v: gint) -> Result<Self, enumeration::UnknownValue> {{
+        match v {{
+", name = e.name).unwrap();
+        for m in members.iter() {
+            writeln!(s, "            {} => Ok({}::{}),",
+                     m.value, e.name, camel_case(&m.name)).unwrap();
+        }
+        write!(s, "            _ => Err(enumeration::UnknownValue(v))
+        }}
+    }}
+
+    fn to_int(&self) -> gint {{
+        *self as gint
+    }}
+
+    fn name(&self) -> &'static str {{
+        match *self {{
+").unwrap();
+        for m in members.iter() {
+            writeln!(s, "            {}::{} => \"{}\",",
+                     e.name, camel_case(&m.name), m.nick).unwrap();
+        }
+        s.push_str("        }\n    }\n}\n");
+        out.push(s);
+
+        if let Some(ref get_type) = e.get_type {
+            out.push(format!("\
+impl enumeration::EnumType for {name} {{
+    fn get_type() -> GType {{
+        unsafe {{ GType::from_raw(ffi::{get_type}()) }}
+    }}
+}}
+", name = e.name, get_type = get_type));
+        }
+
+        if let Some(ref domain) = e.error_domain {
+            out.push(format!("\
+impl error::Domain for {name} {{
+    fn domain() -> quark::Quark {{
+        g_static_quark!(b\"{domain}\\0\")
+    }}
+}}
+", name = e.name, domain = domain));
+        }
+
+        out.push(format!("\
+impl fmt::Display for {name} {{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {{
+        write!(f, \"{{}}\", self.name())
+    }}
+}}
+", name = e.name));
+    }
+
+    pub fn flags_module_name(e: &Enumeration) -> String {
+        let base = if e.name.ends_with("Flags") {
+            &e.name[.. e.name.len() - 5]
+        } else {
+            &e.name[..]
+        };
+        snake_case(base)
+    }
+
+    fn emit_flags(&self, e: &Enumeration, out: &mut Vec<String>) {
+        let get_type = match e.get_type {
+            Some(ref f) => f,
+            None => panic!("flags type {} has no get_type function", e.name)
+        };
+        let module = Generator::flags_module_name(e);
+        let width = e.members.iter().map(|m| m.name.len()).max().unwrap_or(0);
+        let mut s = String::new();
+        write!(s, "\
+pub mod {module} {{
+    use grust::flags::prelude::*;
+    use ffi;
+
+    bitflags! {{
+        pub flags Flags: ::grust::types::guint {{
+", module = module).unwrap();
+        for m in e.members.iter() {
+            let name = m.name.to_uppercase();
+            writeln!(s, "            const {:2$} = {},", name, m.value, width)
+                .unwrap();
+        }
+        write!(s, "        }}
+    }}
+
+    impl IntrospectedFlags for Flags {{
+
+        fn from_uint(v: guint) -> Result<Flags, UnknownFlags> {{
+            Flags::from_bits(v)
+                .ok_or_else(|| UnknownFlags::new(v, Flags::all().bits()))
+        }}
+
+        #[inline]
+        fn to_uint(&self) -> guint {{
+            self.bits()
+        }}
+    }}
+
+    impl FlagsType for Flags {{
+        fn get_type() -> GType {{
+            unsafe {{
+                let raw = ffi::{get_type}();
+                GType::from_raw(raw)
+            }}
+        }}
+    }}
+}}
+", get_type = get_type).unwrap();
+        out.push(s);
+    }
+
+    pub fn generate(&self) -> Output {
+        let ns = self.namespace();
+        let mut output = Output {
+            wrappers: Vec::new(),
+            enums: Vec::new(),
+            flags: Vec::new(),
+            flags_reexports: Vec::new(),
+            cast_traits: Vec::new(),
+            object_types: Vec::new(),
+            upcasts: Vec::new(),
+            as_refs: Vec::new(),
+            properties: Vec::new(),
+            signals: Vec::new(),
+        };
+
+        for name in self.conf.types.iter() {
+            let t = self.lookup_type(name);
+            self.emit_wrapper(t, &mut output.wrappers);
+            if t.kind == TypeKind::Record {
+                continue;
+            }
+            self.emit_cast_trait(t, &mut output.cast_traits);
+            self.emit_object_type(t, &mut output.object_types);
+            if t.kind == TypeKind::Class {
+                self.emit_upcasts(t, &mut output.upcasts);
+            }
+            self.emit_as_refs(t, &mut output.as_refs);
+            if t.kind == TypeKind::Class {
+                self.emit_properties(t, &mut output.properties);
+                self.emit_signals(t, &mut output.signals);
+            }
+        }
+
+        for name in self.conf.enums.iter() {
+            match ns.find_enum(name) {
+                Some(e) => self.emit_enum(e, &mut output.enums),
+                None => panic!("enumeration {}.{} not found", ns.name, name)
+            }
+        }
+
+        for name in self.conf.flags.iter() {
+            match ns.find_bitfield(name) {
+                Some(e) => {
+                    self.emit_flags(e, &mut output.flags);
+                    output.flags_reexports.push(format!(
+                        "pub use flags::{}::Flags as {};\n",
+                        Generator::flags_module_name(e), e.name));
+                }
+                None => panic!("bit field {}.{} not found", ns.name, name)
+            }
+        }
+
+        output
+    }
+
+    fn emit_header(&self, output: &Output, s: &mut String) {
+        let conf = self.conf;
+        s.push_str(LICENSE_HEADER);
+        write!(s, "
+// Generated by grust-proof-gen from {}-{}.gir
+
+#![crate_name = \"{}\"]
+#![crate_type = \"lib\"]
+
+#![allow(trivial_numeric_casts)]
+
+#[macro_use]
+extern crate grust;
+", conf.namespace, conf.version, conf.crate_name).unwrap();
+        if !output.flags.is_empty() {
+            s.push_str("\n#[macro_use]\nextern crate bitflags;\n");
+        }
+        s.push('\n');
+        writeln!(s, "extern crate {} as ffi;",
+                 sys_crate_name(conf.namespace, conf.version)).unwrap();
+        let deps: Vec<_> = config::DEPENDENCIES.iter()
+                           .take_while(|d| d.0 != conf.namespace)
+                           .collect();
+        for &&(ns, _, ffi) in deps.iter() {
+            writeln!(s, "extern crate {} as {};",
+                     sys_crate_name(ns, "2.0"), ffi).unwrap();
+        }
+        for &&(ns, krate, _) in deps.iter() {
+            let dep_conf = config::ALL.iter().find(|c| c.namespace == ns)
+                           .unwrap();
+            writeln!(s, "extern crate {} as {};", dep_conf.crate_name, krate)
+                .unwrap();
+        }
+        s.push('\n');
+        if !output.enums.is_empty() {
+            s.push_str("use grust::enumeration;\n");
+            s.push_str("use grust::enumeration::IntrospectedEnum \
+                        as _grust_IntrospectedEnumTrait;\n");
+            s.push_str("use grust::error;\n");
+        }
+        s.push_str("use grust::gtype::GType;\n");
+        if !output.object_types.is_empty() {
+            s.push_str("use grust::object;\n");
+        }
+        if !output.enums.is_empty() {
+            s.push_str("use grust::quark;\n");
+            s.push_str("use grust::types::gint;\n");
+        }
+        s.push_str("use grust::wrap;\n");
+        if !output.enums.is_empty() {
+            s.push_str("\nuse std::fmt;\n");
+        }
+    }
+
+    /// Produces the complete source of the crate.
+    pub fn crate_source(&self) -> String {
+        let output = self.generate();
+        let mut s = String::new();
+        self.emit_header(&output, &mut s);
+
+        for item in output.wrappers.iter().chain(output.enums.iter()) {
+            s.push('\n');
+            s.push_str(item);
+        }
+
+        if !output.flags.is_empty() {
+            s.push_str("\npub mod flags {\n");
+            for item in output.flags.iter() {
+                s.push('\n');
+                push_indented(&mut s, item);
+            }
+            s.push_str("}\n\n");
+            for item in output.flags_reexports.iter() {
+                s.push_str(item);
+            }
+        }
+
+        if !output.cast_traits.is_empty() {
+            s.push_str("\npub mod cast {\n    use grust::object;\n");
+            for &(_, krate, _) in config::DEPENDENCIES.iter() {
+                let prefix = format!("{}::", krate);
+                if output.cast_traits.iter().any(|t| t.contains(&prefix)) {
+                    write!(s, "\n    use {};\n", krate).unwrap();
+                }
+            }
+            for item in output.cast_traits.iter() {
+                s.push('\n');
+                push_indented(&mut s, item);
+            }
+            s.push_str("}\n");
+        }
+
+        for item in output.object_types.iter()
+                           .chain(output.upcasts.iter())
+                           .chain(output.as_refs.iter())
+                           .chain(output.properties.iter())
+                           .chain(output.signals.iter()) {
+            s.push('\n');
+            s.push_str(item);
+        }
+
+        s
+    }
+}
+
+fn push_indented(s: &mut String, item: &str) {
+    for line in item.lines() {
+        if !line.is_empty() {
+            s.push_str("    ");
+            s.push_str(line);
+        }
+        s.push('\n');
+    }
+}
@@ -0,0 +1,516 @@
+// This file is part of Grust, GObject introspection bindings for Rust
+//
+// Copyright (C) 2016  Mikhail Zabaluev <mikhail.zabaluev@gmail.com>
+//
+// This library is free software; you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation; either
+// version 2.1 of the License, or (at your option) any later version.
+//
+// This library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public
+// License along with this library; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+
+//! A minimal model of GIR repositories, covering just the parts
+//! that the generator currently emits code for.
+
+use xml::attribute::OwnedAttribute;
+use xml::reader::{EventReader, XmlEvent};
+
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Xml(String),
+    Missing(&'static str, String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref e) => write!(f, "{}", e),
+            Error::Xml(ref msg) => write!(f, "malformed GIR: {}", msg),
+            Error::Missing(attr, ref elem) => {
+                write!(f, "attribute {} missing on element {}", attr, elem)
+            }
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            Error::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error { Error::Io(e) }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TypeKind {
+    Class,
+    Interface,
+    Record,
+}
+
+#[derive(Debug)]
+pub struct Property {
+    pub name: String,
+    /// Name of the value type, if given by a `<type>` element.
+    pub type_name: Option<String>,
+    pub readable: bool,
+    pub writable: bool,
+    pub construct_only: bool,
+    /// Whether the value may be NULL.
+    pub nullable: bool,
+}
+
+#[derive(Debug)]
+pub struct ReturnValue {
+    /// Name of the return type, if given by a `<type>` element.
+    pub type_name: Option<String>,
+    /// Whether the value may be NULL.
+    pub nullable: bool,
+}
+
+// Functions are not emitted yet, and signals with parameters
+// are left to hand-written code, so not all of this is used
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct Parameter {
+    pub name: String,
+    /// Name of the parameter type, if given by a `<type>` element.
+    pub type_name: Option<String>,
+    /// Whether NULL may be passed, or an out parameter may be
+    /// given as NULL to ignore the value.
+    pub nullable: bool,
+}
+
+/// A method, constructor or static function of a type.
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct Function {
+    pub name: String,
+    pub c_identifier: Option<String>,
+    pub return_value: ReturnValue,
+    /// Parameters besides the instance, if any.
+    pub parameters: Vec<Parameter>,
+    pub throws: bool,
+}
+
+#[derive(Debug)]
+pub struct Signal {
+    pub name: String,
+    pub return_value: ReturnValue,
+    /// Parameters besides the instance.
+    pub parameters: Vec<Parameter>,
+}
+
+#[derive(Debug)]
+pub struct Type {
+    pub kind: TypeKind,
+    pub name: String,
+    pub c_type: String,
+    pub symbol_prefix: Option<String>,
+    pub get_type: Option<String>,
+    pub parent: Option<String>,
+    pub first_field: Option<String>,
+    /// Names of the interfaces implemented by a class.
+    pub implements: Vec<String>,
+    pub properties: Vec<Property>,
+    pub signals: Vec<Signal>,
+    pub functions: Vec<Function>,
+}
+
+#[derive(Debug)]
+pub struct Member {
+    pub name: String,
+    pub value: i64,
+    pub nick: String,
+}
+
+#[derive(Debug)]
+pub struct Enumeration {
+    pub name: String,
+    pub get_type: Option<String>,
+    pub error_domain: Option<String>,
+    pub members: Vec<Member>,
+}
+
+#[derive(Debug)]
+pub struct Namespace {
+    pub name: String,
+    pub types: Vec<Type>,
+    pub enums: Vec<Enumeration>,
+    pub bitfields: Vec<Enumeration>,
+}
+
+impl Namespace {
+
+    pub fn find_type(&self, name: &str) -> Option<&Type> {
+        self.types.iter().find(|t| t.name == name)
+    }
+
+    pub fn find_enum(&self, name: &str) -> Option<&Enumeration> {
+        self.enums.iter().find(|e| e.name == name)
+    }
+
+    pub fn find_bitfield(&self, name: &str) -> Option<&Enumeration> {
+        self.bitfields.iter().find(|e| e.name == name)
+    }
+}
+
+/// A set of loaded namespaces, used to resolve references
+/// across GIR files.
+pub struct Repository {
+    namespaces: HashMap<String, Namespace>,
+}
+
+impl Repository {
+
+    pub fn new() -> Repository {
+        Repository { namespaces: HashMap::new() }
+    }
+
+    /// Loads `<name>-<version>.gir` from the given directory.
+    pub fn load(&mut self, dir: &Path, name: &str, version: &str)
+                -> Result<(), Error>
+    {
+        let path = dir.join(format!("{}-{}.gir", name, version));
+        let file = File::open(&path)?;
+        let ns = parse(BufReader::new(file))?;
+        self.namespaces.insert(ns.name.clone(), ns);
+        Ok(())
+    }
+
+    pub fn namespace(&self, name: &str) -> &Namespace {
+        match self.namespaces.get(name) {
+            Some(ns) => ns,
+            None => panic!("namespace {} is not loaded", name)
+        }
+    }
+
+    /// Resolves a possibly qualified type name, such as
+    /// `"GObject.Object"`, relative to the namespace `ns`.
+    /// Returns the namespace name and the type.
+    pub fn resolve<'a>(&'a self, ns: &'a str, name: &'a str)
+                       -> (&'a str, &'a Type)
+    {
+        let (ns, name) = match name.find('.') {
+            Some(pos) => (&name[.. pos], &name[pos + 1 ..]),
+            None      => (ns, name)
+        };
+        match self.namespace(ns).find_type(name) {
+            Some(t) => (ns, t),
+            None => panic!("type {}.{} not found", ns, name)
+        }
+    }
+}
+
+fn attr(attrs: &[OwnedAttribute], prefix: Option<&str>, name: &str)
+        -> Option<String>
+{
+    attrs.iter()
+         .find(|a| a.name.local_name == name
+                   && a.name.prefix.as_ref().map(|s| &s[..]) == prefix)
+         .map(|a| a.value.clone())
+}
+
+fn required_attr(attrs: &[OwnedAttribute],
+                 prefix: Option<&str>,
+                 name: &'static str,
+                 elem: &str)
+                 -> Result<String, Error>
+{
+    attr(attrs, prefix, name)
+        .ok_or_else(|| Error::Missing(name, elem.to_string()))
+}
+
+fn flag_attr(attrs: &[OwnedAttribute], name: &str, default: bool) -> bool {
+    match attr(attrs, None, name) {
+        Some(value) => value == "1",
+        None => default
+    }
+}
+
+// Older GIR files use allow-none for both nullable values
+// and optional out parameters.
+fn nullable_attr(attrs: &[OwnedAttribute]) -> bool {
+    flag_attr(attrs, "nullable", false)
+        || flag_attr(attrs, "allow-none", false)
+        || flag_attr(attrs, "optional", false)
+}
+
+fn parse_property(attrs: &[OwnedAttribute]) -> Result<Property, Error> {
+    let name = required_attr(attrs, None, "name", "property")?;
+    Ok(Property {
+        name,
+        type_name: None,
+        readable: flag_attr(attrs, "readable", true),
+        writable: flag_attr(attrs, "writable", false),
+        construct_only: flag_attr(attrs, "construct-only", false),
+        nullable: nullable_attr(attrs),
+    })
+}
+
+fn parse_function(attrs: &[OwnedAttribute], elem: &str)
+                  -> Result<Function, Error>
+{
+    let name = required_attr(attrs, None, "name", elem)?;
+    Ok(Function {
+        name,
+        c_identifier: attr(attrs, Some("c"), "identifier"),
+        return_value: ReturnValue { type_name: None, nullable: false },
+        parameters: Vec::new(),
+        throws: flag_attr(attrs, "throws", false),
+    })
+}
+
+fn parse_parameter(attrs: &[OwnedAttribute]) -> Result<Parameter, Error> {
+    let name = required_attr(attrs, None, "name", "parameter")?;
+    Ok(Parameter {
+        name,
+        type_name: None,
+        nullable: nullable_attr(attrs),
+    })
+}
+
+// Returns the return value and parameters of the function
+// or signal being parsed.
+fn callable<'a>(function: &'a mut Option<Function>,
+                signal: &'a mut Option<Signal>)
+                -> Option<(&'a mut ReturnValue, &'a mut Vec<Parameter>)>
+{
+    if let Some(ref mut f) = *function {
+        return Some((&mut f.return_value, &mut f.parameters));
+    }
+    if let Some(ref mut s) = *signal {
+        return Some((&mut s.return_value, &mut s.parameters));
+    }
+    None
+}
+
+fn parse_member(attrs: &[OwnedAttribute]) -> Result<Member, Error> {
+    let name = required_attr(attrs, None, "name", "member")?;
+    let value = required_attr(attrs, None, "value", "member")?;
+    let value = value.parse::<i64>().map_err(|_| {
+        Error::Xml(format!("invalid value of member {}: {}", name, value))
+    })?;
+    let nick = attr(attrs, Some("glib"), "nick")
+               .unwrap_or_else(|| name.replace("_", "-"));
+    Ok(Member { name, value, nick })
+}
+
+pub fn parse<R>(source: R) -> Result<Namespace, Error>
+    where R: io::Read
+{
+    let mut ns: Option<Namespace> = None;
+    let mut stack: Vec<String> = Vec::new();
+    let mut cur_type: Option<Type> = None;
+    let mut cur_enum: Option<(bool, Enumeration)> = None;
+    let mut cur_property: Option<Property> = None;
+    let mut cur_signal: Option<Signal> = None;
+    let mut cur_function: Option<Function> = None;
+
+    for event in EventReader::new(source) {
+        match event {
+            Ok(XmlEvent::StartElement { name, attributes, .. }) => {
+                let elem = name.local_name;
+                let in_namespace = stack.last()
+                                        .is_some_and(|s| s == "namespace");
+                match &elem[..] {
+                    "namespace" => {
+                        let name = required_attr(&attributes, None,
+                                                 "name", "namespace")?;
+                        ns = Some(Namespace {
+                            name,
+                            types: Vec::new(),
+                            enums: Vec::new(),
+                            bitfields: Vec::new(),
+                        });
+                    }
+                    "class" | "interface" | "record" if in_namespace => {
+                        let kind = match &elem[..] {
+                            "class"     => TypeKind::Class,
+                            "interface" => TypeKind::Interface,
+                            _           => TypeKind::Record
+                        };
+                        let name = required_attr(&attributes, None,
+                                                 "name", &elem)?;
+                        let c_type = required_attr(&attributes, Some("c"),
+                                                   "type", &name)?;
+                        cur_type = Some(Type {
+                            kind,
+                            name,
+                            c_type,
+                            symbol_prefix: attr(&attributes, Some("c"),
+                                                "symbol-prefix"),
+                            get_type: attr(&attributes, Some("glib"),
+                                           "get-type"),
+                            parent: attr(&attributes, None, "parent"),
+                            first_field: None,
+                            implements: Vec::new(),
+                            properties: Vec::new(),
+                            signals: Vec::new(),
+                            functions: Vec::new(),
+                        });
+                    }
+                    "enumeration" | "bitfield" if in_namespace => {
+                        let name = required_attr(&attributes, None,
+                                                 "name", &elem)?;
+                        cur_enum = Some((elem == "bitfield", Enumeration {
+                            name,
+                            get_type: attr(&attributes, Some("glib"),
+                                           "get-type"),
+                            error_domain: attr(&attributes, Some("glib"),
+                                               "error-domain"),
+                            members: Vec::new(),
+                        }));
+                    }
+                    "field" if stack.len() == 3 => {
+                        if let Some(ref mut t) = cur_type {
+                            if t.first_field.is_none() {
+                                let name = required_attr(&attributes, None,
+                                                         "name", "field")?;
+                                t.first_field = Some(name);
+                            }
+                        }
+                    }
+                    "implements" if stack.len() == 3 => {
+                        if let Some(ref mut t) = cur_type {
+                            let name = required_attr(&attributes, None,
+                                                     "name", "implements")?;
+                            t.implements.push(name);
+                        }
+                    }
+                    "property" if stack.len() == 3
+                        && cur_type.is_some() => {
+                            let p = parse_property(&attributes)?;
+                            cur_property = Some(p);
+                        }
+                    "signal" if stack.len() == 3
+                        && cur_type.is_some() => {
+                            let name = required_attr(&attributes, None,
+                                                     "name", "glib:signal")?;
+                            cur_signal = Some(Signal {
+                                name,
+                                return_value: ReturnValue {
+                                    type_name: None,
+                                    nullable: false,
+                                },
+                                parameters: Vec::new(),
+                            });
+                        }
+                    "method" | "constructor" | "function"
+                        if stack.len() == 3 && cur_type.is_some() => {
+                            let f = parse_function(&attributes, &elem)?;
+                            cur_function = Some(f);
+                        }
+                    "return-value" if stack.len() == 4 => {
+                        let parts = callable(&mut cur_function,
+                                             &mut cur_signal);
+                        if let Some((ret, _)) = parts {
+                            ret.nullable = nullable_attr(&attributes);
+                        }
+                    }
+                    "parameter" if stack.len() == 5
+                                   && stack[4] == "parameters" => {
+                        let parts = callable(&mut cur_function,
+                                             &mut cur_signal);
+                        if let Some((_, params)) = parts {
+                            params.push(parse_parameter(&attributes)?);
+                        }
+                    }
+                    "type" if stack.len() == 5
+                              && stack[4] == "return-value" => {
+                        let parts = callable(&mut cur_function,
+                                             &mut cur_signal);
+                        if let Some((ret, _)) = parts {
+                            ret.type_name = attr(&attributes, None, "name");
+                        }
+                    }
+                    "type" if stack.len() == 6
+                              && stack[5] == "parameter" => {
+                        let parts = callable(&mut cur_function,
+                                             &mut cur_signal);
+                        let param = parts.and_then(|(_, ps)| ps.last_mut());
+                        if let Some(p) = param {
+                            p.type_name = attr(&attributes, None, "name");
+                        }
+                    }
+                    "type" if stack.len() == 4 => {
+                        if let Some(ref mut p) = cur_property {
+                            if p.type_name.is_none() {
+                                p.type_name = attr(&attributes, None, "name");
+                            }
+                        }
+                    }
+                    "member" if stack.len() == 3 => {
+                        if let Some((_, ref mut e)) = cur_enum {
+                            let member = parse_member(&attributes)?;
+                            e.members.push(member);
+                        }
+                    }
+                    _ => {}
+                }
+                stack.push(elem);
+            }
+            Ok(XmlEvent::EndElement { .. }) => {
+                stack.pop();
+                if stack.len() == 3 {
+                    if let Some(p) = cur_property.take() {
+                        if let Some(ref mut t) = cur_type {
+                            t.properties.push(p);
+                        }
+                    }
+                    if let Some(s) = cur_signal.take() {
+                        if let Some(ref mut t) = cur_type {
+                            t.signals.push(s);
+                        }
+                    }
+                    if let Some(f) = cur_function.take() {
+                        if let Some(ref mut t) = cur_type {
+                            t.functions.push(f);
+                        }
+                    }
+                }
+                if stack.len() == 2 {
+                    let ns = match ns {
+                        Some(ref mut ns) => ns,
+                        None => continue
+                    };
+                    if let Some(t) = cur_type.take() {
+                        ns.types.push(t);
+                    }
+                    if let Some((is_bitfield, e)) = cur_enum.take() {
+                        if is_bitfield {
+                            ns.bitfields.push(e);
+                        } else {
+                            ns.enums.push(e);
+                        }
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(e) => return Err(Error::Xml(format!("{}", e)))
+        }
+    }
+
+    ns.ok_or_else(|| Error::Xml("no namespace element".to_string()))
+}
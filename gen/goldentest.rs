@@ -0,0 +1,448 @@
+// This file is part of Grust, GObject introspection bindings for Rust
+//
+// Copyright (C) 2016  Mikhail Zabaluev <mikhail.zabaluev@gmail.com>
+//
+// This library is free software; you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation; either
+// version 2.1 of the License, or (at your option) any later version.
+//
+// This library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public
+// License along with this library; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+
+//! Checks the generator output against the hand-edited crates
+//! under `fake-gen`, which serve as the specification.
+//!
+//! Each group of generated items, such as the wrappers or the cast
+//! traits, must appear in the hand-edited sources as one section,
+//! exactly as emitted. Every item in the sources that looks generated
+//! and is named after an introspected type, enumeration, flags
+//! module or signal must be produced by the generator.
+//!
+//! The GIR files are looked up in the directory given by the
+//! `GIR_DIR` environment variable, or in `/usr/share/gir-1.0`.
+//! If `GIR_DIR` is not set and the default directory does not exist,
+//! the golden tests are skipped with a message.
+
+use crate::config;
+use crate::config::CrateConfig;
+use crate::emit;
+use crate::emit::{camel_case, snake_case};
+use crate::gir;
+
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt::Write;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+// Returns `None` if `GIR_DIR` is not set and the default location
+// does not exist, so that the golden tests can be skipped.
+fn gir_dir() -> Option<PathBuf> {
+    match env::var_os("GIR_DIR") {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => {
+            let dir = Path::new("/usr/share/gir-1.0");
+            if dir.is_dir() {
+                Some(dir.to_path_buf())
+            } else {
+                None
+            }
+        }
+    }
+}
+
+// Beginnings of the lines that start items of the kinds emitted
+// by the generator. Hand-written code must not start its items
+// with these, so that it can be told apart from generated code.
+const GENERATED_ITEM_STARTS: &[&str] = &[
+    "unsafe impl wrap::Wrapper for ",
+    "unsafe impl object::ObjectType for ",
+    "impl object::Upcast<",
+    "impl AsRef<",
+    "impl enumeration::IntrospectedEnum for ",
+    "pub fn prop_",
+    "pub fn set_prop_",
+];
+
+// Beginnings of the lines that start generated items when followed
+// by a name the generator may emit; hand-written code uses these
+// for items of its own.
+const NAMED_ITEM_STARTS: &[&str] = &[
+    "pub struct ",
+    "pub enum ",
+    "pub trait As",
+    "pub mod ",
+    "pub fn connect_",
+    "pub use flags::",
+    "unsafe impl Send for ",
+    "unsafe impl Sync for ",
+    "impl enumeration::EnumType for ",
+    "impl error::Domain for ",
+    "impl fmt::Display for ",
+];
+
+// Introspected types that are deliberately wrapped by hand.
+const HAND_WRITTEN_TYPES: &[&str] = &["Bytes", "ParamSpec"];
+
+// Whitespace is not significant for the comparison, so that
+// alignment and line breaking in the hand-edited sources can vary.
+fn normalize(s: &str) -> String {
+    s.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+fn read_source(conf: &CrateConfig) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+               .join("../fake-gen").join(conf.source);
+    let mut source = String::new();
+    File::open(&path).and_then(|mut f| f.read_to_string(&mut source))
+        .unwrap();
+    source
+}
+
+// Returns the name following `start` at the beginning of the line.
+fn item_name<'a>(line: &'a str, start: &str) -> Option<&'a str> {
+    if !line.starts_with(start) {
+        return None;
+    }
+    let rest = &line[start.len()..];
+    let end = rest.find(|c: char| !c.is_alphanumeric() && c != '_')
+                  .unwrap_or(rest.len());
+    Some(&rest[..end])
+}
+
+// Names of the items the generator may emit for the namespace,
+// whether or not it is configured to.
+fn generated_names(ns: &gir::Namespace) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for t in ns.types.iter() {
+        if HAND_WRITTEN_TYPES.contains(&&t.name[..]) {
+            continue;
+        }
+        names.insert(t.name.clone());
+        for signal in t.signals.iter().filter(|s| emit::emits_signal(s)) {
+            names.insert(signal.name.replace('-', "_"));
+        }
+    }
+    for e in ns.enums.iter() {
+        names.insert(e.name.clone());
+    }
+    for e in ns.bitfields.iter() {
+        names.insert(e.name.clone());
+        names.insert(emit::Generator::flags_module_name(e));
+    }
+    names
+}
+
+// Only items named after a type or property are generated;
+// for example, `impl AsRef<[u8]> for Bytes` is hand-written.
+fn looks_generated(line: &str, names: &HashSet<String>) -> bool {
+    GENERATED_ITEM_STARTS.iter().any(|start| {
+        item_name(line, start)
+            .and_then(|name| name.chars().next())
+            .is_some_and(|c| c.is_alphabetic())
+    }) || NAMED_ITEM_STARTS.iter().any(|start| {
+        item_name(line, start).is_some_and(|name| names.contains(name))
+    })
+}
+
+fn check_crate(conf: &CrateConfig) {
+    let dir = match gir_dir() {
+        Some(dir) => dir,
+        None => {
+            eprintln!("skipping the golden test for fake-gen/{}: \
+                       GIR files not found in /usr/share/gir-1.0; \
+                       set GIR_DIR to point to their location",
+                      conf.source);
+            return;
+        }
+    };
+    let mut repo = gir::Repository::new();
+    for dep in config::ALL.iter() {
+        repo.load(&dir, dep.namespace, dep.version).unwrap();
+    }
+    let output = emit::Generator::new(&repo, conf).generate();
+    let raw_source = read_source(conf);
+    let source = normalize(&raw_source);
+
+    let mut report = String::new();
+    let mut pos = 0;
+    for (name, items) in output.sections() {
+        if items.is_empty() {
+            continue;
+        }
+        let section: String = items.iter().map(|s| normalize(s)).collect();
+        if let Some(i) = source[pos..].find(&section) {
+            pos += i + section.len();
+            continue;
+        }
+        // Point out the items that differ, or tell that the section
+        // is interrupted by other code or out of order
+        let missing: Vec<&str> = items.iter()
+            .filter(|item| !source.contains(&normalize(item)))
+            .map(|item| &item[..])
+            .collect();
+        if missing.is_empty() {
+            write!(report, "{} are not emitted as one section, \
+                            or are out of order\n\n", name).unwrap();
+        } else {
+            write!(report, "{} not found:\n\n{}\n",
+                   name, missing.join("\n")).unwrap();
+        }
+    }
+
+    let names = generated_names(repo.namespace(conf.namespace));
+    let generated: Vec<String> = output.sections().iter()
+        .flat_map(|&(_, items)| items.iter())
+        .map(|s| normalize(s))
+        .collect();
+    let generated = generated.join("\n");
+    // A line repeated more times than in the generator output
+    // is also reported, to catch hand-written copies of items
+    let mut seen = HashMap::new();
+    let extra: Vec<&str> = raw_source.lines()
+        .map(|line| line.trim())
+        .filter(|line| looks_generated(line, &names))
+        .filter(|line| {
+            let line = normalize(line);
+            let count = seen.entry(line.clone()).or_insert(0);
+            *count += 1;
+            *count > generated.matches(&line[..]).count()
+        })
+        .collect();
+    if !extra.is_empty() {
+        write!(report, "items not produced by the generator:\n\n{}\n",
+               extra.join("\n")).unwrap();
+    }
+
+    if !report.is_empty() {
+        panic!("fake-gen/{} does not match the generator output\n\n{}",
+               conf.source, report);
+    }
+}
+
+#[test]
+fn glib() {
+    check_crate(&config::GLIB);
+}
+
+#[test]
+fn gobject() {
+    check_crate(&config::GOBJECT);
+}
+
+#[test]
+fn gio() {
+    check_crate(&config::GIO);
+}
+
+#[test]
+fn case_conversion() {
+    assert_eq!(snake_case("FileAttributeInfo"), "file_attribute_info");
+    assert_eq!(snake_case("IOErrorEnum"), "io_error_enum");
+    assert_eq!(camel_case("not_found"), "NotFound");
+    assert_eq!(camel_case("dbus_error"), "DbusError");
+}
+
+#[test]
+fn parse_enum() {
+    let gir = br#"<?xml version="1.0"?>
+<repository version="1.2"
+            xmlns="http://www.gtk.org/introspection/core/1.0"
+            xmlns:c="http://www.gtk.org/introspection/c/1.0"
+            xmlns:glib="http://www.gtk.org/introspection/glib/1.0">
+  <namespace name="Test" version="1.0">
+    <enumeration name="Error" glib:get-type="test_error_get_type"
+                 c:type="TestError" glib:error-domain="test-error-quark">
+      <member name="failed" value="0" c:identifier="TEST_ERROR_FAILED"/>
+      <member name="broken_pipe" value="1" glib:nick="broken-pipe"/>
+      <member name="closed" value="1" glib:nick="closed"/>
+    </enumeration>
+  </namespace>
+</repository>
+"#;
+    let ns = gir::parse(&gir[..]).unwrap();
+    let e = ns.find_enum("Error").unwrap();
+    assert_eq!(e.get_type, Some("test_error_get_type".to_string()));
+    assert_eq!(e.error_domain, Some("test-error-quark".to_string()));
+    assert_eq!(e.members.len(), 3);
+    assert_eq!(e.members[0].nick, "failed");
+    assert_eq!(e.members[1].value, 1);
+}
+
+#[test]
+fn parse_implements() {
+    let gir = br#"<?xml version="1.0"?>
+<repository version="1.2"
+            xmlns="http://www.gtk.org/introspection/core/1.0"
+            xmlns:c="http://www.gtk.org/introspection/c/1.0"
+            xmlns:glib="http://www.gtk.org/introspection/glib/1.0">
+  <namespace name="Test" version="1.0">
+    <class name="Stream" c:type="TestStream" parent="GObject.Object">
+      <implements name="Seekable"/>
+      <implements name="Gio.Converter"/>
+      <field name="parent_instance"><type name="GObject.Object"/></field>
+    </class>
+  </namespace>
+</repository>
+"#;
+    let ns = gir::parse(&gir[..]).unwrap();
+    let t = ns.find_type("Stream").unwrap();
+    assert_eq!(t.implements, ["Seekable", "Gio.Converter"]);
+    assert_eq!(t.first_field, Some("parent_instance".to_string()));
+}
+
+#[test]
+fn parse_properties() {
+    let gir = br#"<?xml version="1.0"?>
+<repository version="1.2"
+            xmlns="http://www.gtk.org/introspection/core/1.0"
+            xmlns:c="http://www.gtk.org/introspection/c/1.0"
+            xmlns:glib="http://www.gtk.org/introspection/glib/1.0">
+  <namespace name="Test" version="1.0">
+    <class name="Monitor" c:type="TestMonitor" parent="GObject.Object">
+      <property name="rate-limit" writable="1" transfer-ownership="none">
+        <type name="gint" c:type="gint"/>
+      </property>
+      <property name="path" writable="1" construct-only="1">
+        <type name="utf8" c:type="gchar*"/>
+      </property>
+      <property name="secret" readable="0" writable="1">
+        <type name="gboolean" c:type="gboolean"/>
+      </property>
+    </class>
+  </namespace>
+</repository>
+"#;
+    let ns = gir::parse(&gir[..]).unwrap();
+    let props = &ns.find_type("Monitor").unwrap().properties;
+    assert_eq!(props.len(), 3);
+    assert_eq!(props[0].name, "rate-limit");
+    assert_eq!(props[0].type_name, Some("gint".to_string()));
+    assert!(props[0].readable && props[0].writable);
+    assert!(!props[0].construct_only);
+    assert!(props[1].construct_only);
+    assert!(!props[2].readable);
+}
+
+#[test]
+fn parse_signals() {
+    let gir = br#"<?xml version="1.0"?>
+<repository version="1.2"
+            xmlns="http://www.gtk.org/introspection/core/1.0"
+            xmlns:c="http://www.gtk.org/introspection/c/1.0"
+            xmlns:glib="http://www.gtk.org/introspection/glib/1.0">
+  <namespace name="Test" version="1.0">
+    <class name="Monitor" c:type="TestMonitor" parent="GObject.Object">
+      <glib:signal name="cancelled" when="last">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+      </glib:signal>
+      <glib:signal name="changed" when="last">
+        <return-value transfer-ownership="none">
+          <type name="gboolean" c:type="gboolean"/>
+        </return-value>
+        <parameters>
+          <parameter name="file" transfer-ownership="none">
+            <type name="utf8" c:type="gchar*"/>
+          </parameter>
+        </parameters>
+      </glib:signal>
+    </class>
+  </namespace>
+</repository>
+"#;
+    let ns = gir::parse(&gir[..]).unwrap();
+    let signals = &ns.find_type("Monitor").unwrap().signals;
+    assert_eq!(signals.len(), 2);
+    assert_eq!(signals[0].name, "cancelled");
+    assert!(signals[0].parameters.is_empty());
+    assert_eq!(signals[0].return_value.type_name, Some("none".to_string()));
+    assert_eq!(signals[1].name, "changed");
+    assert_eq!(signals[1].parameters.len(), 1);
+    assert_eq!(signals[1].parameters[0].name, "file");
+    assert_eq!(signals[1].return_value.type_name,
+               Some("gboolean".to_string()));
+}
+
+#[test]
+fn parse_nullable() {
+    let gir = br#"<?xml version="1.0"?>
+<repository version="1.2"
+            xmlns="http://www.gtk.org/introspection/core/1.0"
+            xmlns:c="http://www.gtk.org/introspection/c/1.0"
+            xmlns:glib="http://www.gtk.org/introspection/glib/1.0">
+  <namespace name="Test" version="1.0">
+    <class name="File" c:type="TestFile" parent="GObject.Object">
+      <method name="get_path" c:identifier="test_file_get_path">
+        <return-value transfer-ownership="full" nullable="1">
+          <type name="filename" c:type="char*"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="file" transfer-ownership="none">
+            <type name="File" c:type="TestFile*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+      <method name="delete" c:identifier="test_file_delete" throws="1">
+        <return-value transfer-ownership="none">
+          <type name="gboolean" c:type="gboolean"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="file" transfer-ownership="none">
+            <type name="File" c:type="TestFile*"/>
+          </instance-parameter>
+          <parameter name="cancellable" transfer-ownership="none"
+                     allow-none="1">
+            <type name="Cancellable" c:type="TestCancellable*"/>
+          </parameter>
+          <parameter name="etag" direction="out" transfer-ownership="full"
+                     optional="1">
+            <type name="utf8" c:type="char**"/>
+          </parameter>
+          <parameter name="flags" transfer-ownership="none">
+            <type name="guint" c:type="guint"/>
+          </parameter>
+        </parameters>
+      </method>
+      <property name="charset" writable="1" nullable="1">
+        <type name="utf8" c:type="gchar*"/>
+      </property>
+      <property name="name" writable="1">
+        <type name="utf8" c:type="gchar*"/>
+      </property>
+    </class>
+  </namespace>
+</repository>
+"#;
+    let ns = gir::parse(&gir[..]).unwrap();
+    let t = ns.find_type("File").unwrap();
+    assert_eq!(t.functions.len(), 2);
+    let get_path = &t.functions[0];
+    assert_eq!(get_path.c_identifier, Some("test_file_get_path".to_string()));
+    assert!(get_path.return_value.nullable);
+    assert_eq!(get_path.return_value.type_name,
+               Some("filename".to_string()));
+    assert!(get_path.parameters.is_empty());
+    assert!(!get_path.throws);
+    let delete = &t.functions[1];
+    assert!(!delete.return_value.nullable);
+    assert!(delete.throws);
+    let params: Vec<(&str, bool)> = delete.parameters.iter()
+        .map(|p| (&p.name[..], p.nullable))
+        .collect();
+    assert_eq!(params, [("cancellable", true), ("etag", true),
+                        ("flags", false)]);
+    assert_eq!(delete.parameters[0].type_name,
+               Some("Cancellable".to_string()));
+    assert!(t.properties[0].nullable);
+    assert!(!t.properties[1].nullable);
+}
@@ -0,0 +1,71 @@
+// This file is part of Grust, GObject introspection bindings for Rust
+//
+// Copyright (C) 2016  Mikhail Zabaluev <mikhail.zabaluev@gmail.com>
+//
+// This library is free software; you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation; either
+// version 2.1 of the License, or (at your option) any later version.
+//
+// This library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public
+// License along with this library; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+
+//! Generates the crates under `fake-gen` from GIR files.
+//!
+//! Usage: `grust-proof-gen GIR_DIR OUT_DIR`
+//!
+//! For now, only the type wrappers, the `cast` traits and the
+//! `ObjectType`/`Upcast` implementations, enumerations and flags
+//! are generated. Methods and callback trampolines remain hand-written
+//! in `fake-gen` until the generator learns to emit them.
+
+mod config;
+mod emit;
+mod gir;
+
+#[cfg(test)]
+mod goldentest;
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process;
+
+fn run(gir_dir: &Path, out_dir: &Path)
+       -> Result<(), Box<dyn std::error::Error>>
+{
+    let mut repo = gir::Repository::new();
+    for conf in config::ALL.iter() {
+        repo.load(gir_dir, conf.namespace, conf.version)?;
+    }
+    for conf in config::ALL.iter() {
+        let gen = emit::Generator::new(&repo, conf);
+        let path = out_dir.join(conf.source);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(&path)?;
+        file.write_all(gen.crate_source().as_bytes())?;
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 3 {
+        let _ = writeln!(std::io::stderr(),
+                         "Usage: {} GIR_DIR OUT_DIR", args[0]);
+        process::exit(2);
+    }
+    if let Err(e) = run(Path::new(&args[1]), Path::new(&args[2])) {
+        let _ = writeln!(std::io::stderr(), "{}: {}", args[0], e);
+        process::exit(1);
+    }
+}
@@ -16,40 +16,138 @@
 // License along with this library; if not, write to the Free Software
 // Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
 
-use gio::{File, FileInputStream, InputStream, IOErrorEnum};
-use gio::cast::AsFile;
+use gio::{BufferedInputStream, BufferedOutputStream, Cancellable};
+use gio::{CharsetConverter, ConverterInputStream};
+use gio::{ConverterOutputStream, ConverterResult};
+use gio::{DataInputStream, DataOutputStream};
+use gio::{DataStreamByteOrder, DataStreamNewlineType};
+use gio::{File, FileAttributeValue, FileInfo, FileInputStream};
+use gio::{FileMonitorEvent, FileQueryInfoFlags, FileType, InputStream};
+use gio::{IOErrorEnum, MemoryInputStream, MemoryOutputStream};
+use gio::{ZlibCompressor, ZlibCompressorFormat, ZlibDecompressor};
+use gio::future::FileInfoStream;
+use gio::flags::{converter, file_copy, file_create, file_monitor};
+use gio::flags::output_stream_splice;
+use gio::cast::{AsConverter, AsFile, AsInputStream, AsIOStream};
+use gio::cast::{AsFilterInputStream, AsOutputStream, AsSeekable};
+use glib::SeekType;
+use glib::executor;
+use glib::executor::Executor;
+use glib::timeout;
+use glib::unwind;
+use gobject::{PropertyError, PropertyType, SignalError};
+use gobject::cast::AsObject;
+use gobject::flags::param;
+use gobject::downcast::{Downcast, DowncastRef};
+use gobject::upcast::IntoUpcast;
+use futures_core::Stream;
 use grust::enumeration;
+use grust::enumeration::IntrospectedEnum;
 use grust::error;
 use grust::error::{Error, DomainError};
+use grust::gstr;
 use grust::refcount::Ref;
-use grust::mainloop::{LoopRunner,MainLoop};
+use grust::mainloop::{LoopRunner, MainLoop};
 use grust::object;
 use grust::value::Value;
 
+use std::io;
+use std::cell::Cell;
+use std::env;
+use std::ffi::{CStr, OsStr};
+use std::fs;
+use std::future;
+use std::future::Future;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::net;
+use std::path::Path;
+use std::pin::Pin;
 use std::str;
+use std::rc::Rc;
+use std::sync::{mpsc, Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll, Wake, Waker};
 
 #[cfg(unix)]
 const NULL_FILE_NAME: &'static str = "/dev/null";
 #[cfg(windows)]
 const NULL_FILE_NAME: &'static str = "\\dev\\null";
 
+// Milliseconds to wait for a file monitor event before failing
+const MONITOR_TIMEOUT: u32 = 5000;
+
 fn run_on_mainloop<F>(setup: F) where F: FnOnce(Ref<MainLoop>) {
-    let runner = LoopRunner::new();
-    runner.run_after(setup);
+    unwind::run_loop(setup);
+}
+
+struct QuitWaker(Ref<MainLoop>);
+
+impl Wake for QuitWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.quit();
+    }
+}
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) { }
+}
+
+// Polls the future once on a main loop that quits when the future
+// is woken, then expects it to be ready.
+fn poll_on_mainloop<F, T>(setup: F) -> T::Output
+    where F: FnOnce() -> T,
+          T: Future + Unpin
+{
+    let mut future = None;
+    run_on_mainloop(|mainloop| {
+        let mut f = setup();
+        let waker = Waker::from(Arc::new(QuitWaker(mainloop)));
+        let poll = Pin::new(&mut f).poll(&mut Context::from_waker(&waker));
+        assert!(poll.is_pending());
+        future = Some(f);
+    });
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut f = future.unwrap();
+    match Pin::new(&mut f).poll(&mut Context::from_waker(&waker)) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future was woken, but is not ready")
+    }
+}
+
+fn read_error_code(path: &'static gstr::Utf8) -> error::Code<IOErrorEnum> {
+    let (tx, rx) = mpsc::channel();
+    run_on_mainloop(|mainloop| {
+        let f = File::new_for_path(path);
+        f.read_async(0, None,
+            move |obj, res| {
+                let f: &File = object::cast(obj);
+                let err = f.read_finish(res).err().unwrap();
+                let reid: Result<DomainError<IOErrorEnum>, Error>
+                          = err.into_domain();
+                match reid {
+                    Ok(io_error) => tx.send(io_error.code()).unwrap(),
+                    Err(_e) => unreachable!()
+                }
+                mainloop.quit();
+            });
+    });
+    rx.recv().unwrap()
 }
 
 #[test]
 fn as_file() {
     let f = File::new_for_path(g_utf8!("/dev/null"));
     let g = f.as_file();
-    let path = g.get_path();
+    let path = g.get_path().unwrap();
     assert_eq!(str::from_utf8(path.to_bytes()).unwrap(), NULL_FILE_NAME);
 }
 
 #[test]
 fn deref() {
     let f = File::new_for_path(g_utf8!("/dev/null"));
-    let path = f.get_path();
+    let path = f.get_path().unwrap();
     assert_eq!(str::from_utf8(path.to_bytes()).unwrap(), NULL_FILE_NAME);
 }
 
@@ -57,7 +155,7 @@ fn deref() {
 fn new_ref() {
     let f = File::new_for_path(g_utf8!("/dev/null"));
     let g = Ref::new(&*f);
-    let path = g.get_path();
+    let path = g.get_path().unwrap();
     assert_eq!(str::from_utf8(path.to_bytes()).unwrap(), NULL_FILE_NAME);
 }
 
@@ -65,7 +163,7 @@ fn new_ref() {
 fn clone() {
     let rf = File::new_for_path(g_utf8!("/dev/null"));
     let rg = rf.clone();
-    let path = rg.get_path();
+    let path = rg.get_path().unwrap();
     assert_eq!(str::from_utf8(path.to_bytes()).unwrap(), NULL_FILE_NAME);
 }
 
@@ -77,7 +175,40 @@ fn cast_fail() {
 }
 
 #[test]
-fn async() {
+fn try_cast_fail() {
+    let rf = File::new_for_path(g_utf8!("/dev/null"));
+    let err = rf.try_cast::<FileInputStream>().err().unwrap();
+    assert!(err.to_string().ends_with("is not a GFileInputStream"));
+    assert!(rf.downcast_ref::<FileInputStream>().is_none());
+    assert!(rf.downcast_ref::<File>().is_some());
+}
+
+#[test]
+fn try_cast_source_object() {
+    run_on_mainloop(|mainloop| {
+        let f = File::new_for_path(g_utf8!("/dev/null"));
+        f.read_async(0, None,
+            move |obj, res| {
+                assert!(obj.try_cast::<InputStream>().is_err());
+                let f = obj.try_cast::<File>().unwrap();
+                assert!(f.read_finish(res).is_ok());
+                mainloop.quit();
+            });
+    });
+}
+
+#[test]
+fn downcast_ref_counted() {
+    let rf = File::new_for_path(g_utf8!("/dev/null"));
+    let obj = Ref::new(rf.as_object());
+    let obj = obj.downcast::<FileInputStream>().err().unwrap();
+    let f = obj.downcast::<File>().ok().unwrap();
+    let path = f.get_path().unwrap();
+    assert_eq!(str::from_utf8(path.to_bytes()).unwrap(), NULL_FILE_NAME);
+}
+
+#[test]
+fn read_async() {
     run_on_mainloop(|mainloop| {
         let f = File::new_for_path(g_utf8!("/dev/null"));
         f.read_async(0, None,
@@ -146,3 +277,1521 @@ fn value_enum() {
             });
     })
 }
+
+#[test]
+fn error_not_found() {
+    assert_eq!(read_error_code(g_utf8!("./does-not-exist")),
+               error::Code::Known(IOErrorEnum::NotFound));
+}
+
+#[test]
+fn error_is_directory() {
+    assert_eq!(read_error_code(g_utf8!(".")),
+               error::Code::Known(IOErrorEnum::IsDirectory));
+}
+
+#[test]
+#[cfg(unix)]
+fn error_not_directory() {
+    assert_eq!(read_error_code(g_utf8!("/dev/null/does-not-exist")),
+               error::Code::Known(IOErrorEnum::NotDirectory));
+}
+
+fn io_error_code(err: Error) -> error::Code<IOErrorEnum> {
+    let io_error: DomainError<IOErrorEnum> = err.into_domain().ok().unwrap();
+    io_error.code()
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn error_permission_denied() {
+    // sysfs does not allow creating files, even by the superuser
+    let f = File::new_for_path(g_utf8!("/sys/kernel/giotest.tmp"));
+    let err = f.create(file_create::NONE, None).err().unwrap();
+    assert_eq!(io_error_code(err),
+               error::Code::Known(IOErrorEnum::PermissionDenied));
+}
+
+// The bindings have no sockets or pollable streams for GIO to fail
+// with these codes, so the errors from std are mapped as GIO would
+// map them when reported by the OS.
+
+#[test]
+#[cfg(unix)]
+fn from_errno_would_block() {
+    use std::os::unix::net::UnixStream;
+
+    let (mut reader, _writer) = UnixStream::pair().unwrap();
+    reader.set_nonblocking(true).unwrap();
+    let err = reader.read(&mut [0u8; 1]).err().unwrap();
+    assert_eq!(IOErrorEnum::from_errno(err.raw_os_error().unwrap()),
+               Ok(IOErrorEnum::WouldBlock));
+}
+
+#[test]
+fn from_errno_connection_refused() {
+    let port = {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().port()
+    };
+    // The listener is closed, so nothing accepts on the port
+    let err = net::TcpStream::connect(("127.0.0.1", port)).err().unwrap();
+    assert_eq!(IOErrorEnum::from_errno(err.raw_os_error().unwrap()),
+               Ok(IOErrorEnum::ConnectionRefused));
+}
+
+#[test]
+fn io_error_enum_codes() {
+    let mut count = 0;
+    while let Ok(code) = IOErrorEnum::from_int(count) {
+        assert_eq!(code.to_int(), count);
+        assert!(!code.name().is_empty());
+        count += 1;
+    }
+    // No gaps before the last code
+    assert_eq!(count, IOErrorEnum::DestinationUnset.to_int() + 1);
+    assert_eq!(IOErrorEnum::NoSuchDevice.name(), "no-such-device");
+    assert_eq!(IOErrorEnum::TimedOut.name(), "timed-out");
+    assert_eq!(IOErrorEnum::from_int(44), Ok(IOErrorEnum::BrokenPipe));
+}
+
+#[test]
+fn io_error_enum_unknown() {
+    // Codes added in later versions of GIO are not errors,
+    // they just can't be matched by name.
+    let unknown = IOErrorEnum::from_int(1000).err().unwrap();
+    assert_eq!(unknown.0, 1000);
+    assert!(IOErrorEnum::from_int(-1).is_err());
+}
+
+#[test]
+fn read_stream() {
+    run_on_mainloop(|mainloop| {
+        let f = File::new_for_path(g_utf8!("Cargo.toml"));
+        f.read_async(0, None,
+            move |obj, res| {
+                let f: &File = object::cast(obj);
+                let stream = f.read_finish(res).ok().unwrap();
+                let mut reader = BufReader::new(&*stream);
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                assert_eq!(line, "[package]\n");
+                mainloop.quit();
+            });
+    })
+}
+
+#[test]
+fn seek_stream() {
+    run_on_mainloop(|mainloop| {
+        let f = File::new_for_path(g_utf8!("Cargo.toml"));
+        f.read_async(0, None,
+            move |obj, res| {
+                let f: &File = object::cast(obj);
+                let stream = f.read_finish(res).ok().unwrap();
+                let mut reader = &*stream;
+                assert_eq!(reader.seek(SeekFrom::Start(1)).unwrap(), 1);
+                let mut buf = [0u8; 7];
+                reader.read_exact(&mut buf).unwrap();
+                assert_eq!(&buf, b"package");
+                assert_eq!(reader.seek(SeekFrom::Current(0)).unwrap(), 8);
+                mainloop.quit();
+            });
+    })
+}
+
+#[test]
+fn copy_stream() {
+    run_on_mainloop(|mainloop| {
+        let f = File::new_for_path(g_utf8!("/dev/null"));
+        f.read_async(0, None,
+            move |obj, res| {
+                let f: &File = object::cast(obj);
+                let stream = f.read_finish(res).ok().unwrap();
+                let mut out = Vec::new();
+                let n = io::copy(&mut &*stream, &mut out).unwrap();
+                assert_eq!(n, 0);
+                assert!(out.is_empty());
+                mainloop.quit();
+            });
+    })
+}
+
+#[test]
+fn read_closed_stream() {
+    run_on_mainloop(|mainloop| {
+        let f = File::new_for_path(g_utf8!("Cargo.toml"));
+        f.read_async(0, None,
+            move |obj, res| {
+                let f: &File = object::cast(obj);
+                let stream = f.read_finish(res).ok().unwrap();
+                let input = stream.as_input_stream();
+                assert_eq!(input.skip(1, None).ok(), Some(1));
+                assert!(input.close(None).is_ok());
+                let mut buf = [0u8; 1];
+                let err = (&*stream).read(&mut buf).err().unwrap();
+                assert_eq!(err.kind(), io::ErrorKind::Other);
+                mainloop.quit();
+            });
+    })
+}
+
+fn read_to_string(path: &str) -> String {
+    let mut s = String::new();
+    fs::File::open(path).unwrap().read_to_string(&mut s).unwrap();
+    s
+}
+
+#[test]
+fn replace_and_append() {
+    let f = File::new_for_path(g_utf8!("target/giotest-replace.tmp"));
+    {
+        let out = f.replace(None, false, file_create::NONE, None)
+                   .ok().unwrap();
+        (&*out).write_all(b"Hello, ").unwrap();
+        assert!(out.as_output_stream().close(None).is_ok());
+    }
+    {
+        let out = f.append_to(file_create::NONE, None).ok().unwrap();
+        (&*out).write_all(b"world!").unwrap();
+        assert!(out.as_output_stream().close(None).is_ok());
+    }
+    assert_eq!(read_to_string("target/giotest-replace.tmp"), "Hello, world!");
+}
+
+#[test]
+fn create_exists() {
+    let f = File::new_for_path(g_utf8!("target/giotest-create.tmp"));
+    let _ = f.replace(None, false, file_create::NONE, None).ok().unwrap();
+    let err = f.create(file_create::NONE, None).err().unwrap();
+    let reid: Result<DomainError<IOErrorEnum>, Error> = err.into_domain();
+    match reid {
+        Ok(io_error) => {
+            assert_eq!(io_error.code(),
+                       error::Code::Known(IOErrorEnum::Exists));
+        }
+        Err(_e) => unreachable!()
+    }
+}
+
+#[test]
+fn replace_async() {
+    run_on_mainloop(|mainloop| {
+        let f = File::new_for_path(g_utf8!("target/giotest-replace-async.tmp"));
+        f.replace_async(None, false, file_create::PRIVATE, 0, None,
+            move |obj, res| {
+                let f: &File = object::cast(obj);
+                let out = f.replace_finish(res).ok().unwrap();
+                write!(&*out, "{}", 42).unwrap();
+                assert!(out.as_output_stream().close(None).is_ok());
+                mainloop.quit();
+            });
+    });
+    assert_eq!(read_to_string("target/giotest-replace-async.tmp"), "42");
+}
+
+#[test]
+fn read_future() {
+    let res = poll_on_mainloop(|| {
+        let f = File::new_for_path(g_utf8!("/dev/null"));
+        f.read_future(0)
+    });
+    let stream = res.ok().unwrap();
+    assert!(object::is_instance_of::<FileInputStream, InputStream>(&*stream));
+}
+
+#[test]
+fn read_future_error() {
+    let res = poll_on_mainloop(|| {
+        let f = File::new_for_path(g_utf8!("./does-not-exist"));
+        f.read_future(0)
+    });
+    let reid: Result<DomainError<IOErrorEnum>, Error>
+              = res.err().unwrap().into_domain();
+    match reid {
+        Ok(io_error) => {
+            assert_eq!(io_error.code(),
+                       error::Code::Known(IOErrorEnum::NotFound));
+        }
+        Err(_e) => unreachable!()
+    }
+}
+
+#[test]
+fn block_on() {
+    let context = executor::new_context();
+    let res = context.block_on(executor::lazy(|| {
+        let f = File::new_for_path(g_utf8!("/dev/null"));
+        f.read_future(0)
+    }));
+    let stream = res.ok().unwrap();
+    let mut buf = [0u8; 1];
+    assert_eq!((&*stream).read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn block_on_sequence() {
+    let context = executor::new_context();
+    let out = context.block_on(executor::lazy(|| {
+        let f = File::new_for_path(g_utf8!("target/giotest-block-on.tmp"));
+        f.replace_future(None, false, file_create::NONE, 0)
+    })).ok().unwrap();
+    (&*out).write_all(b"block_on").unwrap();
+    assert!(out.as_output_stream().close(None).is_ok());
+    let stream = context.block_on(executor::lazy(|| {
+        let f = File::new_for_path(g_utf8!("target/giotest-block-on.tmp"));
+        f.read_future(0)
+    })).ok().unwrap();
+    let mut s = String::new();
+    (&*stream).read_to_string(&mut s).unwrap();
+    assert_eq!(s, "block_on");
+}
+
+#[test]
+fn async_await() {
+    let context = executor::new_context();
+    let res: Result<Vec<u8>, Error> = context.block_on(async {
+        let f = File::new_for_path(g_utf8!("target/giotest-await.tmp"));
+        let out = f.replace_future(None, false, file_create::NONE, 0).await?;
+        out.as_output_stream()
+           .write_all_future(glib::Bytes::from_static(b"awaited"), 0)
+           .await?;
+        out.as_output_stream().close(None)?;
+        let input = f.read_future(0).await?;
+        let data = input.as_input_stream().read_all_future(16, 0).await?;
+        Ok(data)
+    });
+    assert_eq!(res.unwrap(), b"awaited");
+}
+
+#[test]
+fn spawn_local() {
+    let context = executor::new_context();
+    let done = Rc::new(Cell::new(false));
+    let task_done = done.clone();
+    let mut read = None;
+    let task = future::poll_fn(move |cx| {
+        let f = read.get_or_insert_with(|| {
+            File::new_for_path(g_utf8!("/dev/null")).read_future(0)
+        });
+        match Pin::new(f).poll(cx) {
+            Poll::Ready(res) => {
+                assert!(res.is_ok());
+                task_done.set(true);
+                Poll::Ready(())
+            }
+            Poll::Pending => Poll::Pending
+        }
+    });
+    // The context is owned by this thread while block_on() runs
+    context.block_on(executor::lazy(|| {
+        context.spawn_local(task);
+        future::poll_fn(|cx| {
+            if done.get() {
+                Poll::Ready(())
+            } else {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+    }));
+}
+
+#[test]
+#[should_panic(expected = "not owned by the calling thread")]
+fn spawn_local_unowned() {
+    let context = executor::new_context();
+    context.spawn_local(future::ready(()));
+}
+
+fn assert_cancelled(err: Error) {
+    let reid: Result<DomainError<IOErrorEnum>, Error> = err.into_domain();
+    match reid {
+        Ok(io_error) => {
+            assert_eq!(io_error.code(),
+                       error::Code::Known(IOErrorEnum::Cancelled));
+        }
+        Err(_e) => unreachable!()
+    }
+}
+
+#[test]
+fn cancellable() {
+    let cancellable = Cancellable::new();
+    assert!(!cancellable.is_cancelled());
+    assert!(cancellable.set_error_if_cancelled().is_ok());
+    cancellable.cancel();
+    assert!(cancellable.is_cancelled());
+    assert_cancelled(cancellable.set_error_if_cancelled().err().unwrap());
+    cancellable.reset();
+    assert!(!cancellable.is_cancelled());
+}
+
+#[test]
+fn cancellable_connect() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let cancellable = Cancellable::new();
+    let handler = {
+        let count = count.clone();
+        cancellable.connect(move |c| {
+            assert!(c.is_cancelled());
+            count.fetch_add(1, Ordering::SeqCst);
+        })
+    };
+    cancellable.cancel();
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    cancellable.reset();
+    handler.disconnect();
+    cancellable.cancel();
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn cancel_read_async() {
+    run_on_mainloop(|mainloop| {
+        let cancellable = Cancellable::new();
+        cancellable.cancel();
+        let f = File::new_for_path(g_utf8!("/dev/null"));
+        f.read_async(0, Some(&cancellable),
+            move |obj, res| {
+                let f: &File = object::cast(obj);
+                assert_cancelled(f.read_finish(res).err().unwrap());
+                mainloop.quit();
+            });
+    })
+}
+
+#[test]
+fn drop_future_cancels() {
+    let f = File::new_for_path(g_utf8!("/dev/null"));
+    let future = f.read_future(0);
+    let cancellable = Ref::new(future.cancellable());
+    assert!(!cancellable.is_cancelled());
+    drop(future);
+    assert!(cancellable.is_cancelled());
+}
+
+#[test]
+#[should_panic(expected = "callback panicked")]
+fn async_callback_panic() {
+    run_on_mainloop(|_mainloop| {
+        let f = File::new_for_path(g_utf8!("/dev/null"));
+        f.read_async(0, None, |_, _| panic!("callback panicked"));
+    })
+}
+
+// A panic in a callback dispatched by a loop that was not started
+// with unwind::run_loop cannot be resumed, so it aborts the process.
+// The loop is run in a child process running this test alone.
+#[cfg(unix)]
+#[test]
+fn async_callback_panic_plain_loop() {
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::{Command, Stdio};
+
+    const CHILD_VAR: &str = "GIOTEST_PLAIN_LOOP_CHILD";
+    const SIGABRT: i32 = 6;
+
+    if env::var_os(CHILD_VAR).is_some() {
+        let runner = LoopRunner::new();
+        let mut _timeout = None;
+        runner.run_after(|mainloop| {
+            let quit = mainloop.clone();
+            _timeout = Some(timeout::add_once(5000, move || quit.quit()));
+            let f = File::new_for_path(g_utf8!("/dev/null"));
+            f.read_async(0, None, |_, _| panic!("callback panicked"));
+        });
+        // Only reached if the loop has not been aborted
+        return;
+    }
+    let status = Command::new(env::current_exe().unwrap())
+        .args(&["--exact", "giotest::async_callback_panic_plain_loop"])
+        .env(CHILD_VAR, "1")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .unwrap();
+    assert_eq!(status.signal(), Some(SIGABRT));
+}
+
+#[test]
+#[should_panic(expected = "handler panicked")]
+fn cancelled_handler_panic() {
+    let cancellable = Cancellable::new();
+    let _handler = cancellable.connect(|_| panic!("handler panicked"));
+    cancellable.cancel();
+}
+
+#[test]
+fn query_info() {
+    let f = File::new_for_path(g_utf8!("Cargo.toml"));
+    let info = f.query_info(g_utf8!("standard::*,time::modified,unix::mode"),
+                            FileQueryInfoFlags::empty(), None).unwrap();
+    let metadata = fs::metadata("Cargo.toml").unwrap();
+    assert_eq!(info.get_size(), metadata.len() as i64);
+    assert_eq!(info.get_file_type().unwrap(), FileType::Regular);
+    assert_eq!(info.get_display_name(), Some("Cargo.toml"));
+    assert!(!info.get_content_type().unwrap().is_empty());
+    let mtime = info.get_modification_time().unwrap();
+    let expected = metadata.modified().unwrap();
+    let diff = expected.duration_since(mtime).unwrap();
+    assert!(diff.as_secs() < 1);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        assert_eq!(info.get_unix_mode(), Some(metadata.mode()));
+    }
+    match info.get_attribute(g_utf8!("standard::size")) {
+        FileAttributeValue::Uint64(size) => assert_eq!(size, metadata.len()),
+        other => panic!("unexpected value {:?}", other)
+    }
+    match info.get_attribute(g_utf8!("owner::user")) {
+        FileAttributeValue::Invalid => {}
+        other => panic!("unexpected value {:?}", other)
+    }
+}
+
+#[test]
+fn attribute_non_utf8() {
+    let info = FileInfo::new();
+    let latin1 = CStr::from_bytes_with_nul(b"caf\xe9\0").unwrap();
+    let ascii = CStr::from_bytes_with_nul(b"cafe\0").unwrap();
+    info.set_attribute_string(g_utf8!("test::string"), latin1);
+    match info.get_attribute(g_utf8!("test::string")) {
+        FileAttributeValue::ByteString(s) => assert_eq!(s, b"caf\xe9"),
+        other => panic!("unexpected value {:?}", other)
+    }
+    info.set_attribute_stringv(g_utf8!("test::strv"), &[ascii, latin1]);
+    match info.get_attribute(g_utf8!("test::strv")) {
+        FileAttributeValue::ByteStringv(v) => {
+            assert_eq!(v, [&b"cafe"[..], &b"caf\xe9"[..]]);
+        }
+        other => panic!("unexpected value {:?}", other)
+    }
+    info.set_attribute_stringv(g_utf8!("test::strv"), &[ascii, ascii]);
+    match info.get_attribute(g_utf8!("test::strv")) {
+        FileAttributeValue::Stringv(v) => assert_eq!(v, ["cafe", "cafe"]),
+        other => panic!("unexpected value {:?}", other)
+    }
+}
+
+#[test]
+fn query_info_future() {
+    let res = poll_on_mainloop(|| {
+        let f = File::new_for_path(g_utf8!("/dev/null"));
+        f.query_info_future(g_utf8!("standard::type"),
+                            FileQueryInfoFlags::empty(), 0)
+    });
+    let info = res.ok().unwrap();
+    assert_eq!(info.get_file_type().unwrap(), FileType::Special);
+    assert!(info.get_modification_time().is_none());
+}
+
+fn make_test_dir(path: &str, names: &[&str]) {
+    fs::create_dir_all(path).unwrap();
+    for name in names {
+        fs::File::create(format!("{}/{}", path, name)).unwrap();
+    }
+}
+
+#[test]
+fn enumerate_children() {
+    make_test_dir("target/giotest-enumerate.tmp", &["a", "b", "c"]);
+    let dir = File::new_for_path(g_utf8!("target/giotest-enumerate.tmp"));
+    let enumerator = dir.enumerate_children(g_utf8!("standard::name"),
+                                            FileQueryInfoFlags::empty(),
+                                            None).unwrap();
+    let mut names = Vec::new();
+    for res in &*enumerator {
+        let info = res.unwrap();
+        let name = info.get_display_name().unwrap().to_string();
+        let child = enumerator.get_child(&info);
+        let path = child.get_path().unwrap();
+        let path = str::from_utf8(path.to_bytes()).unwrap();
+        assert!(path.ends_with(&format!("giotest-enumerate.tmp/{}", name)));
+        names.push(name);
+    }
+    names.sort();
+    assert_eq!(names, ["a", "b", "c"]);
+    assert!(enumerator.close(None).is_ok());
+    assert!(enumerator.is_closed());
+    let mut iter = (&*enumerator).into_iter();
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn enumerate_children_stream() {
+    make_test_dir("target/giotest-enumerate-stream.tmp", &["a", "b", "c"]);
+    let context = executor::new_context();
+    let enumerator = context.block_on(executor::lazy(|| {
+        let dir = File::new_for_path(
+                g_utf8!("target/giotest-enumerate-stream.tmp"));
+        dir.enumerate_children_future(g_utf8!("standard::name"),
+                                      FileQueryInfoFlags::empty(), 0)
+    })).unwrap();
+    let mut stream: Option<FileInfoStream> = None;
+    let mut batches = Vec::new();
+    context.block_on(future::poll_fn(|cx| {
+        let stream = stream.get_or_insert_with(|| enumerator.stream(2, 0));
+        loop {
+            match Pin::new(&mut *stream).poll_next(cx) {
+                Poll::Ready(Some(res)) => batches.push(res.unwrap().len()),
+                Poll::Ready(None) => return Poll::Ready(()),
+                Poll::Pending => return Poll::Pending
+            }
+        }
+    }));
+    assert_eq!(batches, [2, 1]);
+}
+
+fn write_file(path: &str, contents: &[u8]) {
+    fs::File::create(path).unwrap().write_all(contents).unwrap();
+}
+
+struct DropCounter(Arc<AtomicUsize>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn copy_move_delete() {
+    write_file("target/giotest-copy-src.tmp", b"copy me");
+    let src = File::new_for_path(g_utf8!("target/giotest-copy-src.tmp"));
+    let copy = File::new_for_path(g_utf8!("target/giotest-copy-dst.tmp"));
+    let moved = File::new_for_path(g_utf8!("target/giotest-copy-moved.tmp"));
+    let mut total = 0;
+    src.copy(&copy, file_copy::OVERWRITE, None,
+             Some(&mut |_, n| total = n)).unwrap();
+    assert_eq!(total, 7);
+    assert_eq!(read_to_string("target/giotest-copy-dst.tmp"), "copy me");
+    copy.move_(&moved, file_copy::OVERWRITE, None, None).unwrap();
+    assert_eq!(read_to_string("target/giotest-copy-moved.tmp"), "copy me");
+    assert!(fs::metadata("target/giotest-copy-dst.tmp").is_err());
+    moved.delete(None).unwrap();
+    assert!(fs::metadata("target/giotest-copy-moved.tmp").is_err());
+    assert!(moved.delete(None).is_err());
+}
+
+#[test]
+fn copy_async_progress() {
+    write_file("target/giotest-copy-async-src.tmp", b"copy me too");
+    let progress = Arc::new(AtomicUsize::new(0));
+    let dropped = Arc::new(AtomicUsize::new(0));
+    run_on_mainloop(|mainloop| {
+        let src = File::new_for_path(
+                g_utf8!("target/giotest-copy-async-src.tmp"));
+        let dst = File::new_for_path(
+                g_utf8!("target/giotest-copy-async-dst.tmp"));
+        let progress = progress.clone();
+        let counter = DropCounter(dropped.clone());
+        src.copy_async(&dst, file_copy::OVERWRITE, 0, None,
+            move |current, _| {
+                let _ = &counter;
+                progress.store(current as usize, Ordering::SeqCst);
+            },
+            move |obj, res| {
+                let f: &File = object::cast(obj);
+                f.copy_finish(res).unwrap();
+                mainloop.quit();
+            });
+    });
+    assert_eq!(progress.load(Ordering::SeqCst), 11);
+    assert_eq!(dropped.load(Ordering::SeqCst), 1);
+    assert_eq!(read_to_string("target/giotest-copy-async-dst.tmp"),
+               "copy me too");
+}
+
+#[test]
+fn copy_async_cancelled() {
+    let dropped = Arc::new(AtomicUsize::new(0));
+    run_on_mainloop(|mainloop| {
+        let cancellable = Cancellable::new();
+        cancellable.cancel();
+        let src = File::new_for_path(g_utf8!("Cargo.toml"));
+        let dst = File::new_for_path(
+                g_utf8!("target/giotest-copy-cancelled.tmp"));
+        let counter = DropCounter(dropped.clone());
+        src.copy_async(&dst, file_copy::OVERWRITE, 0, Some(&cancellable),
+            move |_, _| { let _ = &counter; },
+            move |obj, res| {
+                let f: &File = object::cast(obj);
+                assert_cancelled(f.copy_finish(res).err().unwrap());
+                mainloop.quit();
+            });
+    });
+    assert_eq!(dropped.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn file_futures() {
+    write_file("target/giotest-future-src.tmp", b"future copy");
+    let _ = fs::remove_dir_all("target/giotest-future-dir.tmp");
+    let context = executor::new_context();
+    let total = Rc::new(Cell::new(0));
+    let res: Result<(), Error> = context.block_on(async {
+        let src = File::new_for_path(g_utf8!("target/giotest-future-src.tmp"));
+        let dst = File::new_for_path(g_utf8!("target/giotest-future-dst.tmp"));
+        let progress = total.clone();
+        src.copy_future(&dst, file_copy::OVERWRITE, 0,
+                        move |_, n| progress.set(n)).await?;
+        src.delete_future(0).await?;
+        let dir = File::new_for_path(g_utf8!("target/giotest-future-dir.tmp"));
+        dir.make_directory_future(0).await?;
+        assert!(dir.make_directory_future(0).await.is_err());
+        Ok(())
+    });
+    res.unwrap();
+    assert_eq!(total.get(), 11);
+    assert_eq!(read_to_string("target/giotest-future-dst.tmp"),
+               "future copy");
+    assert!(fs::metadata("target/giotest-future-src.tmp").is_err());
+    assert!(fs::metadata("target/giotest-future-dir.tmp").unwrap().is_dir());
+}
+
+#[test]
+fn make_directory() {
+    let _ = fs::remove_dir_all("target/giotest-mkdir.tmp");
+    let dir = File::new_for_path(g_utf8!("target/giotest-mkdir.tmp"));
+    dir.make_directory(None).unwrap();
+    assert!(dir.make_directory(None).is_err());
+    let nested = File::new_for_path(g_utf8!("target/giotest-mkdir.tmp/a/b"));
+    assert!(nested.make_directory(None).is_err());
+    nested.make_directory_with_parents(None).unwrap();
+    assert!(fs::metadata("target/giotest-mkdir.tmp/a/b").unwrap().is_dir());
+}
+
+#[cfg(unix)]
+#[test]
+fn make_symbolic_link() {
+    let _ = fs::remove_file("target/giotest-symlink.tmp");
+    let link = File::new_for_path(g_utf8!("target/giotest-symlink.tmp"));
+    link.make_symbolic_link(g_utf8!("../Cargo.toml"), None).unwrap();
+    let target = fs::read_link("target/giotest-symlink.tmp").unwrap();
+    assert_eq!(target.to_str(), Some("../Cargo.toml"));
+}
+
+#[test]
+fn delete_async() {
+    write_file("target/giotest-delete-async.tmp", b"");
+    run_on_mainloop(|mainloop| {
+        let f = File::new_for_path(g_utf8!("target/giotest-delete-async.tmp"));
+        f.delete_async(0, None,
+            move |obj, res| {
+                let f: &File = object::cast(obj);
+                f.delete_finish(res).unwrap();
+                mainloop.quit();
+            });
+    });
+    assert!(fs::metadata("target/giotest-delete-async.tmp").is_err());
+}
+
+#[test]
+fn monitor_changed() {
+    make_test_dir("target/giotest-monitor.tmp", &[]);
+    let _ = fs::remove_file("target/giotest-monitor.tmp/new");
+    let mut _handler = None;
+    let mut _monitor = None;
+    let mut _timeout = None;
+    run_on_mainloop(|mainloop| {
+        _timeout = Some(timeout::add_once(MONITOR_TIMEOUT, || {
+            panic!("timed out waiting for a Created event");
+        }));
+        let dir = File::new_for_path(g_utf8!("target/giotest-monitor.tmp"));
+        let monitor = dir.monitor_directory(file_monitor::NONE, None)
+                         .unwrap();
+        _handler = Some(monitor.connect_changed(
+            move |_, file, _, event| {
+                if event == FileMonitorEvent::Created {
+                    let path = file.get_path().unwrap();
+                    let path = str::from_utf8(path.to_bytes()).unwrap();
+                    assert!(path.ends_with("giotest-monitor.tmp/new"));
+                    mainloop.quit();
+                }
+            }));
+        _monitor = Some(monitor);
+        write_file("target/giotest-monitor.tmp/new", b"");
+    });
+}
+
+#[test]
+fn monitor_stream() {
+    make_test_dir("target/giotest-monitor-stream.tmp", &[]);
+    let _ = fs::remove_file("target/giotest-monitor-stream.tmp/new");
+    let context = executor::new_context();
+    let mut monitored = None;
+    let event = context.block_on(future::poll_fn(|cx| {
+        let &mut (_, ref mut stream, _) = monitored.get_or_insert_with(|| {
+            let timeout = timeout::add_once(MONITOR_TIMEOUT, || {
+                panic!("timed out waiting for a Created event");
+            });
+            let dir = File::new_for_path(
+                    g_utf8!("target/giotest-monitor-stream.tmp"));
+            let monitor = dir.monitor_directory(file_monitor::NONE, None)
+                             .unwrap();
+            let stream = monitor.changes();
+            write_file("target/giotest-monitor-stream.tmp/new", b"");
+            (monitor, stream, timeout)
+        });
+        loop {
+            match Pin::new(&mut *stream).poll_next(cx) {
+                Poll::Ready(Some((_, _, FileMonitorEvent::Created))) => {
+                    return Poll::Ready(FileMonitorEvent::Created);
+                }
+                Poll::Ready(Some(_)) => {}
+                Poll::Ready(None) => unreachable!(),
+                Poll::Pending => return Poll::Pending
+            }
+        }
+    }));
+    assert_eq!(event, FileMonitorEvent::Created);
+    let (monitor, _, _) = monitored.unwrap();
+    assert!(monitor.cancel());
+    assert!(monitor.is_cancelled());
+}
+
+#[test]
+fn new_for_uri() {
+    let f = File::new_for_uri(g_utf8!("file:///dev/null"));
+    assert_eq!(f.to_path_buf().unwrap(), Path::new("/dev/null"));
+    let uri = f.get_uri();
+    assert_eq!(str::from_utf8(uri.to_bytes()).unwrap(), "file:///dev/null");
+    let f = File::parse_name(g_utf8!("file:///dev/null"));
+    assert_eq!(f.get_basename().unwrap(), OsStr::new("null"));
+}
+
+#[test]
+fn new_for_commandline_arg() {
+    let f = File::new_for_commandline_arg(OsStr::new("Cargo.toml"));
+    let expected = env::current_dir().unwrap().join("Cargo.toml");
+    assert_eq!(f.to_path_buf().unwrap(), expected);
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_path() {
+    use std::os::unix::ffi::OsStrExt;
+    let name = OsStr::from_bytes(b"giotest-\xff.tmp");
+    let path = Path::new("target").join(name);
+    let f = File::from_path(&path);
+    let expected = env::current_dir().unwrap().join(&path);
+    assert_eq!(f.to_path_buf().unwrap(), expected);
+    assert_eq!(f.get_basename().unwrap(), name);
+}
+
+#[test]
+fn parent_and_child() {
+    let dir = File::from_path(Path::new("/dev"));
+    let null = dir.get_child(Path::new("null"));
+    assert_eq!(null.to_path_buf().unwrap(), Path::new("/dev/null"));
+    let parent = null.get_parent().unwrap();
+    assert_eq!(parent.to_path_buf().unwrap(), Path::new("/dev"));
+    let root = parent.get_parent().unwrap();
+    assert!(root.get_parent().is_none());
+    let f = dir.resolve_relative_path(Path::new("../etc"));
+    assert_eq!(f.to_path_buf().unwrap(), Path::new("/etc"));
+    let f = dir.resolve_relative_path(Path::new("/tmp"));
+    assert_eq!(f.to_path_buf().unwrap(), Path::new("/tmp"));
+}
+
+#[test]
+fn new_tmp() {
+    let (f, iostream) = File::new_tmp(Some(OsStr::new("giotest-XXXXXX")))
+                        .unwrap();
+    let path = f.to_path_buf().unwrap();
+    {
+        let out = iostream.as_io_stream().get_output_stream();
+        (&*out).write_all(b"temporary").unwrap();
+        assert!(iostream.as_io_stream().close(None).is_ok());
+    }
+    let basename = f.get_basename().unwrap();
+    assert!(basename.to_str().unwrap().starts_with("giotest-"));
+    assert_eq!(read_to_string(path.to_str().unwrap()), "temporary");
+    f.delete(None).unwrap();
+}
+
+#[test]
+fn non_native_uri() {
+    let f = File::new_for_uri(g_utf8!("resource:///org/gi-rust/test.txt"));
+    assert!(f.get_path().is_none());
+    assert!(f.to_path_buf().is_none());
+    let uri = f.get_uri();
+    assert_eq!(str::from_utf8(uri.to_bytes()).unwrap(),
+               "resource:///org/gi-rust/test.txt");
+    let f = File::new_for_uri(g_utf8!("http://example.com/index.html"));
+    assert!(f.get_path().is_none());
+    assert_eq!(f.get_basename().unwrap(), OsStr::new("index.html"));
+}
+
+#[test]
+fn bytes() {
+    let bytes = glib::Bytes::from(b"Hello".to_vec());
+    assert_eq!(&*bytes, b"Hello");
+    let clone = bytes.clone();
+    assert_eq!(clone.as_ref().as_ptr(), bytes.as_ref().as_ptr());
+    drop(bytes);
+    assert_eq!(clone, glib::Bytes::from_static(b"Hello"));
+    assert!(glib::Bytes::from(Vec::new()).is_empty());
+}
+
+#[test]
+fn load_contents() {
+    let f = File::new_for_path(g_utf8!("Cargo.toml"));
+    let (contents, etag) = f.load_contents(None).unwrap();
+    assert_eq!(&*contents, read_to_string("Cargo.toml").as_bytes());
+    assert!(etag.is_some());
+}
+
+#[test]
+fn replace_contents() {
+    let f = File::new_for_path(g_utf8!("target/giotest-contents.tmp"));
+    let etag = f.replace_contents(b"first", None, false, file_create::NONE,
+                                  None).unwrap();
+    assert!(etag.is_some());
+    let (contents, _) = f.load_contents(None).unwrap();
+    assert_eq!(&*contents, b"first");
+}
+
+#[test]
+fn replace_contents_bytes_future() {
+    let context = executor::new_context();
+    let bytes = glib::Bytes::from(b"second".to_vec());
+    let etag = context.block_on(executor::lazy(|| {
+        let f = File::new_for_path(
+                g_utf8!("target/giotest-contents-bytes.tmp"));
+        f.replace_contents_bytes_future(&bytes, None, false,
+                                        file_create::NONE)
+    })).unwrap();
+    assert!(etag.is_some());
+    let (contents, _) = context.block_on(executor::lazy(|| {
+        let f = File::new_for_path(
+                g_utf8!("target/giotest-contents-bytes.tmp"));
+        f.load_contents_future()
+    })).unwrap();
+    assert_eq!(contents, bytes);
+}
+
+#[test]
+fn memory_input_stream() {
+    let bytes = glib::Bytes::from_static(b"first line\nsecond line\n");
+    let stream = MemoryInputStream::from_bytes(&bytes);
+    stream.add_bytes(&glib::Bytes::from(b"third line\n".to_vec()));
+    let mut reader = BufReader::new(&*stream);
+    let lines: Vec<String> = reader.by_ref().lines()
+                                   .map(|l| l.unwrap()).collect();
+    assert_eq!(lines, ["first line", "second line", "third line"]);
+    let input = stream.as_input_stream();
+    assert!(input.close(None).is_ok());
+    let mut buf = [0u8; 1];
+    assert!(input.read(&mut buf, None).is_err());
+}
+
+#[test]
+fn memory_output_stream() {
+    let out = MemoryOutputStream::new_resizable();
+    let input = MemoryInputStream::from_bytes(
+            &glib::Bytes::from_static(b"copied through memory"));
+    let copied = io::copy(&mut &*input, &mut &*out).unwrap();
+    assert_eq!(copied, 21);
+    assert_eq!(out.get_data_size(), 21);
+    assert!(out.as_output_stream().close(None).is_ok());
+    let bytes = out.steal_as_bytes();
+    assert_eq!(&*bytes, b"copied through memory");
+}
+
+#[test]
+#[should_panic(expected = "must be closed")]
+fn memory_output_steal_open() {
+    let out = MemoryOutputStream::new_resizable();
+    out.steal_as_bytes();
+}
+
+#[test]
+fn data_streams() {
+    let mem = MemoryOutputStream::new_resizable();
+    {
+        let out = DataOutputStream::new(mem.as_output_stream());
+        out.set_byte_order(DataStreamByteOrder::LittleEndian);
+        out.put_byte(0xfe, None).unwrap();
+        out.put_int16(-2, None).unwrap();
+        out.put_uint32(0xdeadbeef, None).unwrap();
+        out.put_int64(-3, None).unwrap();
+        out.put_string("key=value\n", None).unwrap();
+        assert!(out.as_output_stream().close(None).is_ok());
+    }
+    let bytes = mem.steal_as_bytes();
+    assert_eq!(&bytes[.. 3], b"\xfe\xfe\xff");
+    let input = DataInputStream::new(
+            MemoryInputStream::from_bytes(&bytes).as_input_stream());
+    input.set_byte_order(DataStreamByteOrder::LittleEndian);
+    assert_eq!(input.read_byte(None).unwrap(), 0xfe);
+    assert_eq!(input.read_int16(None).unwrap(), -2);
+    assert_eq!(input.read_uint32(None).unwrap(), 0xdeadbeef);
+    assert_eq!(input.read_int64(None).unwrap(), -3);
+    assert_eq!(input.read_upto(b"=", None).unwrap().unwrap(), b"key");
+    assert_eq!(input.read_byte(None).unwrap(), b'=');
+    assert_eq!(input.read_line(None).unwrap().unwrap(), b"value");
+    assert!(input.read_line(None).unwrap().is_none());
+    assert!(input.read_int32(None).is_err());
+}
+
+#[test]
+fn data_input_lines() {
+    let bytes = glib::Bytes::from_static(b"one\r\ntwo\nthree");
+    let input = DataInputStream::new(
+            MemoryInputStream::from_bytes(&bytes).as_input_stream());
+    input.set_newline_type(DataStreamNewlineType::Any);
+    let lines: Vec<Vec<u8>> = input.lines().map(|l| l.unwrap()).collect();
+    assert_eq!(lines, [&b"one"[..], b"two", b"three"]);
+}
+
+#[test]
+fn data_input_lines_stream() {
+    let bytes = glib::Bytes::from_static(b"one\ntwo\n");
+    let input = DataInputStream::new(
+            MemoryInputStream::from_bytes(&bytes).as_input_stream());
+    let context = executor::new_context();
+    let mut stream = None;
+    let mut lines = Vec::new();
+    context.block_on(future::poll_fn(|cx| {
+        let stream = stream.get_or_insert_with(|| input.lines_stream(0));
+        loop {
+            match Pin::new(&mut *stream).poll_next(cx) {
+                Poll::Ready(Some(res)) => lines.push(res.unwrap()),
+                Poll::Ready(None) => return Poll::Ready(()),
+                Poll::Pending => return Poll::Pending
+            }
+        }
+    }));
+    assert_eq!(lines, [b"one", b"two"]);
+}
+
+#[test]
+fn zlib_round_trip() {
+    let data: Vec<u8> = (0 .. 10000).map(|i| (i % 7) as u8).collect();
+    let compressed = MemoryOutputStream::new_resizable();
+    {
+        let compressor = ZlibCompressor::new(ZlibCompressorFormat::Gzip, -1);
+        let out = ConverterOutputStream::new(compressed.as_output_stream(),
+                                             compressor.as_converter());
+        (&*out).write_all(&data).unwrap();
+        assert!(out.as_output_stream().close(None).is_ok());
+    }
+    let compressed = compressed.steal_as_bytes();
+    assert_eq!(&compressed[.. 2], b"\x1f\x8b");
+    assert!(compressed.len() < data.len());
+
+    let decompressor = ZlibDecompressor::new(ZlibCompressorFormat::Gzip);
+    let input = ConverterInputStream::new(
+            MemoryInputStream::from_bytes(&compressed).as_input_stream(),
+            decompressor.as_converter());
+    let mut decompressed = Vec::new();
+    (&*input).read_to_end(&mut decompressed).unwrap();
+    assert_eq!(decompressed, data);
+}
+
+#[test]
+fn charset_converter() {
+    let converter = CharsetConverter::new(g_utf8!("UTF-8"),
+                                          g_utf8!("ISO-8859-1")).unwrap();
+    let mut outbuf = [0u8; 16];
+    let (res, read, written) = converter.as_converter()
+        .convert(b"caf\xe9", &mut outbuf, converter::INPUT_AT_END)
+        .unwrap();
+    assert_eq!(res, ConverterResult::Finished);
+    assert_eq!(read, 4);
+    assert_eq!(&outbuf[.. written], "café".as_bytes());
+    assert!(CharsetConverter::new(g_utf8!("UTF-8"),
+                                  g_utf8!("no-such-charset")).is_err());
+}
+
+fn buffered_data(input: &BufferedInputStream) -> Vec<u8> {
+    let mut buf = vec![0; input.get_available()];
+    let n = input.peek(&mut buf, 0);
+    buf.truncate(n);
+    buf
+}
+
+#[test]
+fn buffered_input_stream() {
+    let mem = MemoryInputStream::from_bytes(
+            &glib::Bytes::from_static(b"0123456789"));
+    let input = BufferedInputStream::new_sized(mem.as_input_stream(), 4);
+    assert_eq!(input.get_buffer_size(), 4);
+    assert_eq!(input.fill(None, None).unwrap(), 4);
+    assert_eq!(input.get_available(), 4);
+    assert_eq!(buffered_data(&input), b"0123");
+    let mut buf = [0u8; 2];
+    assert_eq!(input.peek(&mut buf, 1), 2);
+    assert_eq!(&buf, b"12");
+    (&*input).read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"01");
+    input.set_buffer_size(16);
+    assert_eq!(input.fill(Some(8), None).unwrap(), 6);
+    assert_eq!(buffered_data(&input), b"23456789");
+    let base = input.as_filter_input_stream().get_base_stream();
+    assert!(object::is_instance_of::<_, MemoryInputStream>(base));
+}
+
+#[test]
+fn buffered_input_fill_future() {
+    let mem = MemoryInputStream::from_bytes(
+            &glib::Bytes::from_static(b"async fill"));
+    let input = BufferedInputStream::new(mem.as_input_stream());
+    let context = executor::new_context();
+    let n = context.block_on(executor::lazy(|| input.fill_future(None, 0)))
+            .unwrap();
+    assert_eq!(n, 10);
+    assert_eq!(buffered_data(&input), b"async fill");
+}
+
+#[test]
+fn buffered_output_stream() {
+    let mem = MemoryOutputStream::new_resizable();
+    let out = BufferedOutputStream::new_sized(mem.as_output_stream(), 4);
+    out.set_auto_grow(true);
+    assert!(out.get_auto_grow());
+    (&*out).write_all(b"buffered data").unwrap();
+    assert_eq!(mem.get_data_size(), 0);
+    assert!(out.get_buffer_size() >= 13);
+    (&*out).flush().unwrap();
+    assert_eq!(mem.get_data_size(), 13);
+    assert!(out.as_output_stream().close(None).is_ok());
+    assert_eq!(&*mem.steal_as_bytes(), b"buffered data");
+}
+
+#[test]
+fn splice() {
+    let input = MemoryInputStream::from_bytes(
+            &glib::Bytes::from_static(b"spliced contents"));
+    let mem = MemoryOutputStream::new_resizable();
+    let n = mem.as_output_stream()
+               .splice(input.as_input_stream(),
+                       output_stream_splice::CLOSE_SOURCE
+                           | output_stream_splice::CLOSE_TARGET,
+                       None)
+               .unwrap();
+    assert_eq!(n, 16);
+    assert!(mem.as_output_stream().is_closed());
+    assert_eq!(&*mem.steal_as_bytes(), b"spliced contents");
+}
+
+#[test]
+fn splice_future() {
+    write_file("target/giotest-splice-future.tmp", b"pumped on the loop");
+    let f = File::new_for_path(g_utf8!("target/giotest-splice-future.tmp"));
+    let mem = MemoryOutputStream::new_resizable();
+    let context = executor::new_context();
+    let input = context.block_on(executor::lazy(|| f.read_future(0)))
+                       .unwrap();
+    let n = context.block_on(executor::lazy(|| {
+        mem.as_output_stream()
+           .splice_future(input.as_input_stream(),
+                          output_stream_splice::CLOSE_TARGET,
+                          0)
+    })).unwrap();
+    assert_eq!(n, 18);
+    assert_eq!(&*mem.steal_as_bytes(), b"pumped on the loop");
+}
+
+#[test]
+fn read_all() {
+    let input = MemoryInputStream::new();
+    input.add_bytes(&glib::Bytes::from_static(b"read "));
+    input.add_bytes(&glib::Bytes::from_static(b"all"));
+    let mut buf = [0u8; 6];
+    assert_eq!(input.as_input_stream().read_all(&mut buf, None).unwrap(), 6);
+    assert_eq!(&buf, b"read a");
+    assert_eq!(input.as_input_stream().read_all(&mut buf, None).unwrap(), 2);
+    assert_eq!(&buf[.. 2], b"ll");
+}
+
+#[test]
+fn read_all_async() {
+    let input = MemoryInputStream::from_bytes(
+            &glib::Bytes::from_static(b"async read"));
+    run_on_mainloop(|mainloop| {
+        input.as_input_stream().read_all_async(vec![0; 16], 0, None,
+            move |obj, res, mut buf| {
+                let stream: &InputStream = object::cast(obj);
+                let n = stream.read_all_finish(res).unwrap();
+                buf.truncate(n);
+                assert_eq!(&buf[..], b"async read");
+                mainloop.quit();
+            });
+    });
+}
+
+#[test]
+fn read_futures() {
+    let input = MemoryInputStream::from_bytes(
+            &glib::Bytes::from_static(b"0123456789"));
+    let stream = input.as_input_stream();
+    let context = executor::new_context();
+    let data = context.block_on(executor::lazy(|| {
+        stream.read_all_future(4, 0)
+    })).unwrap();
+    assert_eq!(&data[..], b"0123");
+    let bytes = context.block_on(executor::lazy(|| {
+        stream.read_bytes_future(4, 0)
+    })).unwrap();
+    assert_eq!(&*bytes, b"4567");
+    let data = context.block_on(executor::lazy(|| {
+        stream.read_all_future(8, 0)
+    })).unwrap();
+    assert_eq!(&data[..], b"89");
+    let bytes = stream.read_bytes(4, None).unwrap();
+    assert!(bytes.is_empty());
+}
+
+#[test]
+fn write_all_future() {
+    let mem = MemoryOutputStream::new_resizable();
+    let context = executor::new_context();
+    let n = context.block_on(executor::lazy(|| {
+        let data = glib::Bytes::from(b"written in full".to_vec());
+        mem.as_output_stream().write_all_future(data, 0)
+    })).unwrap();
+    assert_eq!(n, 15);
+    assert_eq!(mem.as_output_stream().write_all(b"!", None).unwrap(), 1);
+    assert!(mem.as_output_stream().close(None).is_ok());
+    assert_eq!(&*mem.steal_as_bytes(), b"written in full!");
+}
+
+#[test]
+fn seekable() {
+    let input = MemoryInputStream::from_bytes(
+            &glib::Bytes::from_static(b"0123456789"));
+    let seekable = input.as_seekable();
+    assert!(seekable.can_seek());
+    assert!(!seekable.can_truncate());
+    seekable.seek(4, SeekType::Set, None).unwrap();
+    assert_eq!(seekable.tell(), 4);
+    let mut buf = [0u8; 2];
+    (&*input).read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"45");
+    seekable.seek(-3, SeekType::End, None).unwrap();
+    assert_eq!(seekable.tell(), 7);
+    let pos = io::Seek::seek(&mut &*seekable, SeekFrom::Current(-1));
+    assert_eq!(pos.unwrap(), 6);
+    let pos = io::Seek::seek(&mut &*seekable, SeekFrom::Start(u64::MAX));
+    assert_eq!(pos.err().unwrap().kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(seekable.tell(), 6);
+    let err = seekable.truncate(0, None).err().unwrap();
+    let io_error: DomainError<IOErrorEnum> = err.into_domain().ok().unwrap();
+    assert_eq!(io_error.code(), error::Code::Known(IOErrorEnum::NotSupported));
+}
+
+#[test]
+fn seekable_truncate() {
+    let mem = MemoryOutputStream::new_resizable();
+    (&*mem).write_all(b"truncated").unwrap();
+    let seekable = mem.as_seekable();
+    assert!(seekable.can_truncate());
+    seekable.truncate(5, None).unwrap();
+    assert_eq!(mem.get_data_size(), 5);
+    assert!(mem.as_output_stream().close(None).is_ok());
+    assert_eq!(&*mem.steal_as_bytes(), b"trunc");
+}
+
+#[test]
+fn seekable_file_stream() {
+    let f = File::new_for_path(g_utf8!("Cargo.toml"));
+    run_on_mainloop(|mainloop| {
+        f.read_async(0, None,
+            move |obj, res| {
+                let f: &File = object::cast(obj);
+                let stream = f.read_finish(res).unwrap();
+                let seekable = stream.as_seekable();
+                assert!(seekable.can_seek());
+                seekable.seek(1, SeekType::Set, None).unwrap();
+                let mut buf = [0u8; 7];
+                (&*stream).read_exact(&mut buf).unwrap();
+                assert_eq!(&buf, b"package");
+                assert_eq!(seekable.tell(), 8);
+                mainloop.quit();
+            });
+    });
+}
+
+#[test]
+fn into_upcast() {
+    let input = MemoryInputStream::from_bytes(
+            &glib::Bytes::from_static(b"upcast"));
+    let seekable: Ref<gio::Seekable> = input.clone().into_upcast();
+    seekable.seek(2, SeekType::Set, None).unwrap();
+    let stream: Ref<InputStream> = input.into_upcast();
+    let mut s = String::new();
+    (&*stream).read_to_string(&mut s).unwrap();
+    assert_eq!(s, "cast");
+    let obj: Ref<gobject::Object> = stream.into_upcast();
+    let input = obj.downcast::<MemoryInputStream>().ok().unwrap();
+    assert_eq!(input.as_seekable().tell(), 6);
+}
+
+fn read_to_end<S>(stream: &S) -> Vec<u8> where S: AsRef<InputStream> {
+    let mut buf = Vec::new();
+    (&*stream.as_ref()).read_to_end(&mut buf).unwrap();
+    buf
+}
+
+#[test]
+fn as_ref_hierarchy() {
+    let input = MemoryInputStream::from_bytes(
+            &glib::Bytes::from_static(b"first"));
+    assert_eq!(read_to_end(&*input), b"first");
+    let input = MemoryInputStream::from_bytes(
+            &glib::Bytes::from_static(b"second"));
+    let buffered = BufferedInputStream::new(input.as_input_stream());
+    assert_eq!(read_to_end(&*buffered), b"second");
+    assert_eq!(read_to_end(buffered.as_input_stream()), b"");
+    let seekable: &gio::Seekable = (*input).as_ref();
+    assert_eq!(seekable.tell(), 6);
+}
+
+#[test]
+fn object_properties() {
+    make_test_dir("target/giotest-properties.tmp", &[]);
+    let dir = File::new_for_path(g_utf8!("target/giotest-properties.tmp"));
+    let monitor = dir.monitor_directory(file_monitor::NONE, None).unwrap();
+    monitor.set_prop_rate_limit(100);
+    assert_eq!(monitor.prop_rate_limit(), 100);
+    let obj = monitor.as_object();
+    assert_eq!(obj.get_property::<i32>("rate-limit").unwrap(), 100);
+    obj.set_property("rate-limit", 250i32).unwrap();
+    assert_eq!(monitor.prop_rate_limit(), 250);
+    assert!(!monitor.prop_cancelled());
+    monitor.cancel();
+    assert!(obj.get_property::<bool>("cancelled").unwrap());
+}
+
+#[test]
+fn property_errors() {
+    let conv = CharsetConverter::new(g_utf8!("UTF-8"), g_utf8!("ISO-8859-1"))
+               .unwrap();
+    assert_eq!(conv.prop_from_charset(), "ISO-8859-1");
+    let obj = conv.as_object();
+    match obj.get_property::<i32>("no-such-property") {
+        Err(PropertyError::NotFound { ref class, ref name }) => {
+            assert_eq!(class, "GCharsetConverter");
+            assert_eq!(name, "no-such-property");
+        }
+        other => panic!("unexpected result {:?}", other)
+    }
+    match obj.get_property::<i32>("from-charset") {
+        Err(PropertyError::TypeMismatch { ref property_type,
+                                          ref requested_type, .. }) => {
+            assert_eq!(property_type, "gchararray");
+            assert_eq!(requested_type, "gint");
+        }
+        other => panic!("unexpected result {:?}", other)
+    }
+    match obj.set_property("to-charset", Some("UTF-16".to_string())) {
+        Err(PropertyError::NotWritable { .. }) => {}
+        other => panic!("unexpected result {:?}", other)
+    }
+    obj.set_property("use-fallback", true).unwrap();
+    assert!(conv.get_use_fallback());
+}
+
+#[test]
+fn list_properties() {
+    let input = MemoryInputStream::new();
+    let buffered = BufferedInputStream::new(input.as_input_stream());
+    let props = buffered.as_object().list_properties();
+    let names: Vec<&str> = props.iter().map(|p| p.get_name()).collect();
+    assert!(names.contains(&"base-stream"));
+    assert!(names.contains(&"close-base-stream"));
+    let pspec = buffered.as_object().find_property("buffer-size").unwrap();
+    assert_eq!(pspec.get_name(), "buffer-size");
+    assert!(pspec.get_nick().is_some());
+    assert_eq!(pspec.value_type().to_raw(), u32::value_type().to_raw());
+    let flags = pspec.get_flags();
+    assert!(flags.contains(param::READABLE | param::WRITABLE));
+    assert!(!flags.contains(param::CONSTRUCT_ONLY));
+    assert!(buffered.as_object().find_property("no-such-property").is_none());
+    assert_eq!(buffered.prop_buffer_size() as usize,
+               buffered.get_buffer_size());
+}
+
+#[test]
+fn property_values() {
+    let input = MemoryInputStream::new();
+    let data = DataInputStream::new(input.as_input_stream());
+    let obj = data.as_object();
+    let value = obj.get_property_value("byte-order").unwrap();
+    assert_eq!(value.get_enum(), Ok(DataStreamByteOrder::BigEndian));
+    let mut value = Value::new(enumeration::type_of::<DataStreamByteOrder>());
+    value.set_enum(DataStreamByteOrder::LittleEndian);
+    obj.set_property_value("byte-order", &value).unwrap();
+    assert_eq!(data.get_byte_order(), Ok(DataStreamByteOrder::LittleEndian));
+}
+
+#[test]
+fn connect_notify() {
+    let input = MemoryInputStream::new();
+    let buffered = BufferedInputStream::new(input.as_input_stream());
+    let obj = buffered.as_object();
+    let count = Arc::new(AtomicUsize::new(0));
+    let id = {
+        let count = count.clone();
+        obj.connect_notify(Some("buffer-size"), move |_, pspec| {
+            assert_eq!(pspec.get_name(), "buffer-size");
+            count.fetch_add(1, Ordering::SeqCst);
+        }).unwrap()
+    };
+    buffered.set_buffer_size(1024);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    obj.signal_handler_block(id);
+    buffered.set_buffer_size(2048);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    obj.signal_handler_unblock(id);
+    buffered.set_buffer_size(4096);
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    obj.signal_handler_disconnect(id);
+    assert!(!obj.signal_handler_is_connected(id));
+    buffered.set_buffer_size(1024);
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    match obj.connect_notify(Some("no-such-property"), |_, _| {}) {
+        Err(SignalError::NotFound { ref name, .. }) => {
+            assert_eq!(name, "notify::no-such-property");
+        }
+        other => panic!("unexpected result {:?}", other)
+    }
+}
+
+#[test]
+fn connect_values() {
+    let input = MemoryInputStream::new();
+    let buffered = BufferedInputStream::new(input.as_input_stream());
+    let sizes = Arc::new(Mutex::new(Vec::new()));
+    let id = {
+        let sizes = sizes.clone();
+        buffered.as_object().connect("notify::buffer-size", move |args| {
+            assert_eq!(args.len(), 2);
+            let obj: &gobject::Object = unsafe {
+                gobject::value_as_object(&args[0])
+            };
+            let size = obj.get_property::<u32>("buffer-size").unwrap();
+            sizes.lock().unwrap().push(size);
+            None
+        }).unwrap()
+    };
+    buffered.set_buffer_size(100);
+    buffered.set_buffer_size(200);
+    assert_eq!(*sizes.lock().unwrap(), [100, 200]);
+    buffered.as_object().signal_handler_disconnect(id);
+    match buffered.as_object().connect("no-such-signal", |_| None) {
+        Err(SignalError::NotFound { ref class, ref name }) => {
+            assert_eq!(class, "GBufferedInputStream");
+            assert_eq!(name, "no-such-signal");
+        }
+        other => panic!("unexpected result {:?}", other)
+    }
+}
+
+#[test]
+fn connect_cancelled() {
+    let cancellable = Cancellable::new();
+    let count = Arc::new(AtomicUsize::new(0));
+    let handler = {
+        let count = count.clone();
+        cancellable.connect_cancelled(move |c| {
+            assert!(c.is_cancelled());
+            count.fetch_add(1, Ordering::SeqCst);
+        })
+    };
+    cancellable.cancel();
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    handler.disconnect();
+
+    let cancellable = Cancellable::new();
+    let handler = {
+        let count = count.clone();
+        cancellable.connect_cancelled(move |_| {
+            count.fetch_add(1, Ordering::SeqCst);
+        })
+    };
+    handler.block();
+    cancellable.cancel();
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    drop(handler);
+}
+
+#[test]
+fn emit_by_name() {
+    let cancellable = Cancellable::new();
+    let count = Arc::new(AtomicUsize::new(0));
+    let _handler = {
+        let count = count.clone();
+        cancellable.connect_cancelled(move |_| {
+            count.fetch_add(1, Ordering::SeqCst);
+        })
+    };
+    let obj = cancellable.as_object();
+    assert!(obj.emit_by_name("cancelled", &[]).unwrap().is_none());
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    let arg = Value::new(u32::value_type());
+    match obj.emit_by_name("cancelled", &[&arg]) {
+        Err(SignalError::ArgumentCount { expected, actual, .. }) => {
+            assert_eq!(expected, 0);
+            assert_eq!(actual, 1);
+        }
+        other => panic!("unexpected result {:?}", other)
+    }
+    match obj.emit_by_name("no-such-signal", &[]) {
+        Err(SignalError::NotFound { .. }) => {}
+        other => panic!("unexpected result {:?}", other)
+    }
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+#[should_panic(expected = "notify handler panicked")]
+fn notify_handler_panic() {
+    let input = MemoryInputStream::new();
+    let buffered = BufferedInputStream::new(input.as_input_stream());
+    let obj = buffered.as_object();
+    obj.connect_notify(Some("buffer-size"), |_, _| {
+        panic!("notify handler panicked")
+    }).unwrap();
+    obj.set_property("buffer-size", 100u32).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "signal handler panicked")]
+fn emit_handler_panic() {
+    let cancellable = Cancellable::new();
+    let _handler = cancellable.connect_cancelled(|_| {
+        panic!("signal handler panicked")
+    });
+    cancellable.as_object().emit_by_name("cancelled", &[]).unwrap();
+}
+
+#[test]
+fn signal_closure_freed() {
+    let dropped = Arc::new(AtomicUsize::new(0));
+    {
+        let cancellable = Cancellable::new();
+        let counter = DropCounter(dropped.clone());
+        cancellable.as_object().connect("cancelled", move |_| {
+            let _ = &counter;
+            None
+        }).unwrap();
+        assert_eq!(dropped.load(Ordering::SeqCst), 0);
+    }
+    assert_eq!(dropped.load(Ordering::SeqCst), 1);
+
+    let cancellable = Cancellable::new();
+    let handler = cancellable.connect_cancelled({
+        let counter = DropCounter(dropped.clone());
+        move |_| { let _ = &counter; }
+    });
+    let id = handler.detach();
+    assert!(cancellable.as_object().signal_handler_is_connected(id));
+    cancellable.as_object().signal_handler_disconnect(id);
+    assert_eq!(dropped.load(Ordering::SeqCst), 2);
+}
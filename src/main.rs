@@ -19,6 +19,9 @@
 #[macro_use]
 extern crate grust;
 
+extern crate futures_core;
+extern crate grust_glib_2_0 as glib;
+extern crate grust_gobject_2_0 as gobject;
 extern crate grust_gio_2_0 as gio;
 
 #[cfg(test)]